@@ -1,11 +1,20 @@
-use cosmwasm_std::{Addr, Coin, CosmosMsg, DepsMut, Env, QuerierWrapper, StdResult, Uint128};
+use cosmwasm_std::{Addr, Coin, CosmosMsg, DepsMut, Env, Order, QuerierWrapper, StdResult, Storage, SubMsg, Uint128};
 use cw20::{Cw20QueryMsg, TokenInfoResponse};
+use cw_storage_plus::MultiIndex;
 
 use injective_cosmwasm::{create_new_denom_msg, InjectiveMsgWrapper, InjectiveQuerier, InjectiveQueryWrapper};
 use serde::{Deserialize, Serialize};
 
 use crate::error::ContractError;
-use crate::state::CW20_CONTRACTS;
+use crate::execute_metadata::{build_denom_metadata, save_and_set_denom_metadata};
+use crate::proto::{
+    self, stargate_msg, Metadata, MsgSetBeforeSendHook, MsgSetDenomMetadata, MSG_SET_BEFORE_SEND_HOOK_TYPE_URL, MSG_SET_DENOM_METADATA_TYPE_URL,
+};
+use crate::reply::submsg_with_reply;
+use crate::state::{
+    normalise_token_label, registrations, CodePin, Config, DenomMetadata, PendingReply, Registration, CODE_PINS, CONFIG, CW20_CONTRACTS,
+    FEE_RESERVE_TOTALS, TOKEN_MANAGERS,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AdapterDenom {
@@ -80,6 +89,35 @@ pub fn query_denom_creation_fee(querier_wrapper: &QuerierWrapper<InjectiveQueryW
     Ok(querier.query_token_factory_creation_fee()?.fee)
 }
 
+/// Fee required from the caller of an explicit registration: denom creation fee plus the configured surcharge
+pub fn query_registration_fee(querier_wrapper: &QuerierWrapper<InjectiveQueryWrapper>, storage: &dyn Storage) -> StdResult<Vec<Coin>> {
+    let mut fee = query_denom_creation_fee(querier_wrapper)?;
+    for coin in load_config(storage)?.registration_surcharge {
+        add_coin(&mut fee, &coin);
+    }
+    Ok(fee)
+}
+
+pub fn load_config(storage: &dyn Storage) -> StdResult<Config> {
+    Ok(CONFIG.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn ensure_admin(storage: &dyn Storage, sender: &Addr) -> Result<Config, ContractError> {
+    let config = load_config(storage)?;
+    if config.admin.as_ref() != Some(sender) {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(config)
+}
+
+/// Passes the adapter admin and the manager of `cw20_addr`
+pub fn ensure_admin_or_token_manager(storage: &dyn Storage, sender: &Addr, cw20_addr: &str) -> Result<(), ContractError> {
+    if load_config(storage)?.admin.as_ref() == Some(sender) || TOKEN_MANAGERS.may_load(storage, cw20_addr)?.as_ref() == Some(sender) {
+        return Ok(());
+    }
+    Err(ContractError::Unauthorized)
+}
+
 pub fn fetch_cw20_metadata(deps: &DepsMut<InjectiveQueryWrapper>, addr: &str) -> Result<TokenInfoResponse, ContractError> {
     let msg = Cw20QueryMsg::TokenInfo {};
     deps.querier.query_wasm_smart(addr, &msg).map_err(|_e| ContractError::NotCw20Address)
@@ -102,30 +140,150 @@ pub fn ensure_sufficient_create_denom_balance(deps: &DepsMut<InjectiveQueryWrapp
     let required_funds = query_denom_creation_fee(&deps.querier)?;
 
     for c in required_funds {
-        let balance = deps.querier.query_balance(env.contract.address.as_str(), c.denom)?;
-        if balance.amount < c.amount {
+        let balance = deps.querier.query_balance(env.contract.address.as_str(), c.denom.as_str())?;
+        // funds prepaid by sponsors can only be spent on their behalf
+        let reserved = FEE_RESERVE_TOTALS.may_load(deps.storage, &c.denom)?.unwrap_or_default();
+        if balance.amount.saturating_sub(reserved) < c.amount {
             return Err(ContractError::NotEnoughBalanceToPayDenomCreationFee);
         }
     }
     Ok(())
 }
 
-pub fn register_contract_and_get_message(
+/// Returns true if `funds` contain at least the `required` amount of every denom
+pub fn funds_cover(funds: &[Coin], required: &[Coin]) -> bool {
+    required.iter().all(|r| funds.iter().any(|f| f.denom == r.denom && f.amount >= r.amount))
+}
+
+pub fn add_coin(funds: &mut Vec<Coin>, coin: &Coin) {
+    match funds.iter_mut().find(|f| f.denom == coin.denom) {
+        Some(f) => f.amount += coin.amount,
+        None => funds.push(coin.clone()),
+    }
+}
+
+/// Subtracts `required` from `funds` dropping denoms that reach zero. Caller must ensure `funds_cover(funds, required)`
+pub fn deduct_coins(funds: &mut Vec<Coin>, required: &[Coin]) {
+    for r in required {
+        if let Some(f) = funds.iter_mut().find(|f| f.denom == r.denom) {
+            f.amount = f.amount.saturating_sub(r.amount);
+        }
+    }
+    funds.retain(|f| !f.amount.is_zero());
+}
+
+/// Fills in CW-20 name, symbol and their collision flags of `registration` before saving it. Returns messages that create
+/// the TF denom and set its metadata, in this order. The registration becomes active once the reply confirms the denom
+/// was created
+pub fn register_contract_and_get_messages(
     deps: DepsMut<InjectiveQueryWrapper>,
     env: &Env,
     addr: &Addr,
-) -> Result<CosmosMsg<InjectiveMsgWrapper>, ContractError> {
+    registration: &mut Registration,
+) -> Result<Vec<SubMsg<InjectiveMsgWrapper>>, ContractError> {
     let contract_address = addr.to_string();
-    ensure_address_is_cw20(&deps, &contract_address)?;
-    CW20_CONTRACTS.insert(deps.storage, &contract_address)?;
-    let create_denom_message = create_new_denom_msg(env.contract.address.to_string(), contract_address);
+    let token_info = fetch_cw20_metadata(&deps, &contract_address)?;
+    registration.name = token_info.name.clone();
+    registration.symbol = token_info.symbol.clone();
+    registration.symbol_collision = label_taken(deps.storage, &registrations().idx.symbol, &registration.symbol, &contract_address);
+    registration.name_collision = label_taken(deps.storage, &registrations().idx.name, &registration.name, &contract_address);
+    let code_pin = query_code_pin(&deps.querier, addr)?;
+    registrations().save(deps.storage, &contract_address, registration)?;
+    CODE_PINS.save(deps.storage, &contract_address, &code_pin)?;
+    let metadata = build_denom_metadata(deps.as_ref(), &get_denom(&env.contract.address, addr), addr, token_info)?;
+    let create_denom_message = submsg_with_reply(
+        deps.storage,
+        create_new_denom_msg(env.contract.address.to_string(), contract_address),
+        PendingReply::CreateDenom { cw20: addr.clone() },
+    )?;
+    let set_metadata_message = save_and_set_denom_metadata(deps.storage, env, addr.as_str(), &metadata)?;
+
+    Ok(vec![create_denom_message, SubMsg::new(set_metadata_message)])
+}
+
+/// Returns true if an indexed registration of a CW-20 other than `cw20_addr` already uses the normalised `label`. Empty
+/// labels never collide
+fn label_taken(storage: &dyn Storage, index: &MultiIndex<String, Registration, String>, label: &str, cw20_addr: &str) -> bool {
+    let label = normalise_token_label(label);
+    !label.is_empty()
+        && index
+            .prefix(label)
+            .keys_raw(storage, None, None, Order::Ascending)
+            .any(|key| key != cw20_addr.as_bytes())
+}
+
+/// Brings name and symbol of the registration of `cw20_addr` (and so their indexes) in line with `token_info` and
+/// re-checks collisions of the labels that changed. CW-20s without a registration record are left alone
+pub fn sync_registration_labels(storage: &mut dyn Storage, cw20_addr: &str, token_info: &TokenInfoResponse) -> StdResult<()> {
+    let mut registration = match registrations().may_load(storage, cw20_addr)? {
+        Some(registration) => registration,
+        None => return Ok(()),
+    };
+    if registration.name == token_info.name && registration.symbol == token_info.symbol {
+        return Ok(());
+    }
+    if registration.symbol != token_info.symbol {
+        registration.symbol_collision = label_taken(storage, &registrations().idx.symbol, &token_info.symbol, cw20_addr);
+        registration.symbol = token_info.symbol.clone();
+    }
+    if registration.name != token_info.name {
+        registration.name_collision = label_taken(storage, &registrations().idx.name, &token_info.name, cw20_addr);
+        registration.name = token_info.name.clone();
+    }
+    registrations().save(storage, cw20_addr, &registration)
+}
+
+/// Returns code ID and checksum of the code currently running at `addr`
+pub fn query_code_pin(querier: &QuerierWrapper<InjectiveQueryWrapper>, addr: &Addr) -> StdResult<CodePin> {
+    let code_id = querier.query_wasm_contract_info(addr)?.code_id;
+    let checksum = querier.query_wasm_code_info(code_id)?.checksum;
+    Ok(CodePin { code_id, checksum })
+}
+
+/// Sets (or clears, if `cosmwasm_address` is empty) the contract called by the bank module before every transfer of `denom`
+pub fn create_set_before_send_hook_msg(sender: &Addr, denom: &str, cosmwasm_address: &str) -> CosmosMsg<InjectiveMsgWrapper> {
+    let msg = MsgSetBeforeSendHook {
+        sender: sender.to_string(),
+        denom: denom.to_string(),
+        cosmwasm_address: cosmwasm_address.to_string(),
+    };
+    stargate_msg(MSG_SET_BEFORE_SEND_HOOK_TYPE_URL, &msg)
+}
 
-    Ok(create_denom_message)
+/// Replaces bank metadata of a TF denom administered by `sender`
+pub fn create_set_denom_metadata_msg(sender: &Addr, metadata: &DenomMetadata) -> CosmosMsg<InjectiveMsgWrapper> {
+    let denom_units = metadata
+        .denom_units
+        .iter()
+        .map(|unit| proto::DenomUnit {
+            denom: unit.denom.clone(),
+            exponent: unit.exponent,
+            aliases: vec![],
+        })
+        .collect();
+    let msg = MsgSetDenomMetadata {
+        sender: sender.to_string(),
+        metadata: Some(Metadata {
+            description: metadata.description.clone(),
+            denom_units,
+            base: metadata.base.clone(),
+            display: metadata.display.clone(),
+            name: metadata.name.clone(),
+            symbol: metadata.symbol.clone(),
+            uri: metadata.uri.clone(),
+            uri_hash: String::new(),
+            decimals: metadata.decimals.into(),
+        }),
+    };
+    stargate_msg(MSG_SET_DENOM_METADATA_TYPE_URL, &msg)
 }
 
 #[cfg(test)]
 mod tests {
+    use prost::Message;
+
     use super::*;
+    use crate::state::DenomUnit;
 
     #[test]
     fn it_returns_true_on_correct_token_factory_denom() {
@@ -176,6 +334,41 @@ mod tests {
         assert_eq!(denom, ContractError::NotCw20Address, "cw20 address returned")
     }
 
+    #[test]
+    fn it_encodes_denom_metadata_with_decimals() {
+        let metadata = DenomMetadata {
+            description: "Solana on Injective".to_string(),
+            denom_units: vec![
+                DenomUnit {
+                    denom: "factory/adapter/cw20".to_string(),
+                    exponent: 0,
+                },
+                DenomUnit {
+                    denom: "SOL".to_string(),
+                    exponent: 6,
+                },
+            ],
+            base: "factory/adapter/cw20".to_string(),
+            display: "SOL".to_string(),
+            name: "Solana".to_string(),
+            symbol: "SOL".to_string(),
+            uri: String::new(),
+            decimals: 6,
+        };
+        let (type_url, value) = match create_set_denom_metadata_msg(&Addr::unchecked("adapter"), &metadata) {
+            CosmosMsg::Stargate { type_url, value } => (type_url, value),
+            _ => panic!("expected a stargate message"),
+        };
+        assert_eq!(type_url, MSG_SET_DENOM_METADATA_TYPE_URL, "incorrect type url");
+        let msg = MsgSetDenomMetadata::decode(value.as_slice()).unwrap();
+        assert_eq!(msg.sender, "adapter", "incorrect sender");
+        let encoded = msg.metadata.expect("metadata was not encoded");
+        assert_eq!(encoded.decimals, 6, "decimals were not encoded");
+        assert_eq!(encoded.display, "SOL", "incorrect display unit");
+        assert_eq!(encoded.denom_units.len(), 2, "incorrect denom units");
+        assert_eq!(encoded.denom_units[1].exponent, 6, "incorrect display unit exponent");
+    }
+
     #[test]
     fn it_returns_denom() {
         let denom =
@@ -1,8 +1,13 @@
-use cosmwasm_std::{entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
 use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
 
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::{error::ContractError, execute_metadata, execute_receive, execute_redeem, execute_register, query};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
+use crate::state::{Config, CONFIG};
+use crate::{
+    error::ContractError, execute_access_list, execute_admin, execute_fee_reserve, execute_fees, execute_limits, execute_metadata,
+    execute_rate_limits, execute_receive, execute_redeem, execute_redemption_claims, execute_register, execute_token_manager,
+    execute_token_restrictions, query, reply,
+};
 
 pub const CONTRACT_NAME: &str = "crates.io:inj-cw20-adapter";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -10,10 +15,40 @@ pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut<InjectiveQueryWrapper>,
-    _env: Env,
-    _info: MessageInfo,
-    _msg: InstantiateMsg,
-) -> StdResult<Response<InjectiveMsgWrapper>> {
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let mut config = Config {
+        admin: Some(info.sender.clone()),
+        ..Default::default()
+    };
+    execute_admin::apply_config_update(deps.api, &mut config, msg.config)?;
+    CONFIG.save(deps.storage, &config)?;
+    if msg.cw20s.is_empty() {
+        return Ok(Response::new());
+    }
+    execute_register::handle_register_batch_msg(deps, env, info, msg.cw20s)
+}
+
+/// Adapters deployed before configuration was introduced only know their registered CW-20s. They get their admin here,
+/// state of their tokens is recorded from then on
+#[entry_point]
+pub fn migrate(deps: DepsMut<InjectiveQueryWrapper>, _env: Env, msg: MigrateMsg) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    let previous = cw2::get_contract_version(deps.storage)?;
+    if previous.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigrationSource { contract: previous.contract });
+    }
+    let config = match (CONFIG.may_load(deps.storage)?, msg.admin) {
+        (config, Some(admin)) => Config {
+            admin: Some(deps.api.addr_validate(&admin)?),
+            ..config.unwrap_or_default()
+        },
+        (Some(config), None) => config,
+        (None, None) => return Err(ContractError::MigrationAdminRequired),
+    };
+    CONFIG.save(deps.storage, &config)?;
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::new())
 }
@@ -26,18 +61,96 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
     match msg {
-        ExecuteMsg::RegisterCw20Contract { addr } => execute_register::handle_register_msg(deps, env, info, addr),
+        ExecuteMsg::RegisterCw20Contract { addr, limits } => execute_register::handle_register_msg(deps, env, info, addr, limits),
+        ExecuteMsg::RegisterCw20Contracts { addrs } => execute_register::handle_register_batch_msg(deps, env, info, addrs),
         ExecuteMsg::Receive { sender, amount, msg: _ } => execute_receive::handle_on_received_cw20_funds_msg(deps, env, info, sender, amount),
         ExecuteMsg::RedeemAndTransfer { recipient } => execute_redeem::handle_redeem_msg(deps, env, info, recipient, None),
         ExecuteMsg::RedeemAndSend { recipient, submsg } => execute_redeem::handle_redeem_msg(deps, env, info, Some(recipient), Some(submsg)),
+        ExecuteMsg::RedeemAndTransferOrClaim { recipient } => execute_redeem::handle_redeem_or_claim_msg(deps, env, info, recipient),
+        ExecuteMsg::ClaimRedemption { addr } => execute_redemption_claims::handle_claim_redemption(deps, info, addr),
+        ExecuteMsg::ProcessRedemptionClaims { addr, start_after, limit } => {
+            execute_redemption_claims::handle_process_redemption_claims(deps, info, addr, start_after, limit)
+        }
         ExecuteMsg::UpdateMetadata { addr } => execute_metadata::handle_update_metadata(deps, env, addr),
+        ExecuteMsg::RefreshAllMetadata { start_after, limit } => execute_metadata::handle_refresh_all_metadata(deps, env, info, start_after, limit),
+        ExecuteMsg::SetMetadataOverride { addr, metadata_override } => {
+            execute_metadata::handle_set_metadata_override(deps, env, info, addr, metadata_override)
+        }
+        ExecuteMsg::DepositFeeReserve { for_cw20 } => execute_fee_reserve::handle_deposit_fee_reserve(deps, info, for_cw20),
+        ExecuteMsg::WithdrawFeeReserve { for_cw20 } => execute_fee_reserve::handle_withdraw_fee_reserve(deps, info, for_cw20),
+        ExecuteMsg::UpdateConfig(update) => execute_admin::handle_update_config(deps, info, update),
+        ExecuteMsg::SetTokenFees { addr, fees } => execute_fees::handle_set_token_fees(deps, info, addr, fees),
+        ExecuteMsg::ClaimFees {} => execute_fees::handle_claim_fees(deps, info),
+        ExecuteMsg::SetTokenLimits { addr, limits } => execute_limits::handle_set_token_limits(deps, info, addr, limits),
+        ExecuteMsg::SetRateLimit { addr, rate_limit } => execute_rate_limits::handle_set_rate_limit(deps, info, addr, rate_limit),
+        ExecuteMsg::UpdateBlocklist { add, remove } => execute_access_list::handle_update_blocklist(deps, info, add, remove),
+        ExecuteMsg::UpdateAllowlist { add, remove } => execute_access_list::handle_update_allowlist(deps, info, add, remove),
+        ExecuteMsg::SetTokenFrozen { addr, frozen } => execute_token_restrictions::handle_set_token_frozen(deps, info, addr, frozen),
+        ExecuteMsg::SetSendHook { addr, enabled } => execute_token_restrictions::handle_set_send_hook(deps, env, info, addr, enabled),
+        ExecuteMsg::SetTokenVerified { addr, verified } => execute_token_restrictions::handle_set_token_verified(deps, info, addr, verified),
+        ExecuteMsg::ClaimTokenManager { addr } => execute_token_manager::handle_claim_token_manager(deps, info, addr),
+        ExecuteMsg::SetTokenManager { addr, manager } => execute_token_manager::handle_set_token_manager(deps, info, addr, manager),
+    }
+}
+
+#[entry_point]
+pub fn reply(deps: DepsMut<InjectiveQueryWrapper>, env: Env, msg: Reply) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    reply::handle_reply(deps, env, msg)
+}
+
+#[entry_point]
+pub fn sudo(deps: DepsMut<InjectiveQueryWrapper>, env: Env, msg: SudoMsg) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    match msg {
+        SudoMsg::BlockBeforeSend { from, to, amount } => execute_token_restrictions::handle_block_before_send(deps, env, from, to, amount),
+        SudoMsg::TrackBeforeSend { .. } => Ok(Response::new()),
     }
 }
 
 #[entry_point]
-pub fn query(deps: Deps<InjectiveQueryWrapper>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps<InjectiveQueryWrapper>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::RegisteredContracts {} => to_binary(&query::registered_contracts(deps)?),
+        QueryMsg::RegisteredContractsAtHeight { height, start_after, limit } => {
+            to_binary(&query::registered_contracts_at_height(deps, height, start_after, limit)?)
+        }
+        QueryMsg::Stats { start_after, limit } => to_binary(&query::stats(deps, start_after, limit)?),
         QueryMsg::NewDenomFee {} => to_binary(&query::new_denom_fee(deps)?),
+        QueryMsg::FeeReserves { sponsor } => to_binary(&query::sponsor_fee_reserves(deps, sponsor)?),
+        QueryMsg::Config {} => to_binary(&query::config(deps)?),
+        QueryMsg::Registration { addr } => to_binary(&query::registration(deps, addr)?),
+        QueryMsg::RegistrationsByRegistrant {
+            registrant,
+            start_after,
+            limit,
+        } => to_binary(&query::registrations_by_registrant(deps, registrant, start_after, limit)?),
+        QueryMsg::RegistrationsBySymbol { symbol, start_after, limit } => {
+            to_binary(&query::registrations_by_symbol(deps, symbol, start_after, limit)?)
+        }
+        QueryMsg::VerifiedTokens { start_after, limit } => to_binary(&query::verified_tokens(deps, start_after, limit)?),
+        QueryMsg::RegistrationsByName { name, start_after, limit } => to_binary(&query::registrations_by_name(deps, name, start_after, limit)?),
+        QueryMsg::SimulateWrap { addr, amount } => to_binary(&query::simulate_wrap(deps, addr, amount)?),
+        QueryMsg::SimulateRedeem { addr, amount } => to_binary(&query::simulate_redeem(deps, addr, amount)?),
+        QueryMsg::FeeBalances { beneficiary } => to_binary(&query::fee_balances(deps, beneficiary)?),
+        QueryMsg::RedemptionClaim { addr, claimant } => to_binary(&query::redemption_claim(deps, addr, claimant)?),
+        QueryMsg::RedemptionClaims { addr, start_after, limit } => to_binary(&query::redemption_claims(deps, addr, start_after, limit)?),
+        QueryMsg::TokenLimits { addr } => to_binary(&query::token_limits(deps, addr)?),
+        QueryMsg::Supply { addr } => to_binary(&query::supply(deps, addr)?),
+        QueryMsg::SupplyAtHeight { addr, height } => to_binary(&query::supply_at_height(deps, addr, height)?),
+        QueryMsg::RateLimit { addr } => to_binary(&query::rate_limit(deps, env, addr)?),
+        QueryMsg::Blocklist { start_after, limit } => to_binary(&query::blocklist(deps, start_after, limit)?),
+        QueryMsg::Allowlist { start_after, limit } => to_binary(&query::allowlist(deps, start_after, limit)?),
+        QueryMsg::TokenRestrictions { addr } => to_binary(&query::token_restrictions(deps, addr)?),
+        QueryMsg::CodePin { addr } => to_binary(&query::code_pin(deps, addr)?),
+        QueryMsg::TokenManager { addr } => to_binary(&query::token_manager(deps, addr)?),
+        QueryMsg::MetadataOverride { addr } => to_binary(&query::metadata_override(deps, addr)?),
+        QueryMsg::MetadataHistory { addr, start_after, limit } => to_binary(&query::metadata_history(deps, addr, start_after, limit)?),
+        QueryMsg::OperationsByToken { addr, start_after, limit } => to_binary(&query::operations_by_token(deps, addr, start_after, limit)?),
+        QueryMsg::OperationsByAddress { address, start_after, limit } => to_binary(&query::operations_by_address(deps, address, start_after, limit)?),
+        QueryMsg::Portfolio {
+            address,
+            start_after,
+            limit,
+            hide_zero,
+        } => to_binary(&query::portfolio(deps, env, address, start_after, limit, hide_zero)?),
     }
 }
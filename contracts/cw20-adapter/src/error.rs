@@ -1,3 +1,4 @@
+use cosmwasm_std::Uint128;
 use thiserror::Error;
 
 #[derive(Debug, Error, PartialEq)]
@@ -11,6 +12,21 @@ pub enum ContractError {
     #[error("CW-20 contract with the same address was already registered")]
     ContractAlreadyRegistered,
 
+    #[error("Auto-registration is disabled, CW-20 contract has to be registered first")]
+    AutoRegistrationDisabled,
+
+    #[error("Auto-registration requires a sponsor's fee reserve")]
+    AutoRegistrationNotSponsored,
+
+    #[error("No CW-20 contracts provided")]
+    NoContractsProvided,
+
+    #[error("CW-20 contracts cannot be registered, duplicated: {duplicated:?}, already registered: {already_registered:?}")]
+    BatchRegistrationRejected {
+        duplicated: Vec<String>,
+        already_registered: Vec<String>,
+    },
+
     #[error("CW-20 contract is not registered in adapter")]
     ContractNotRegistered,
 
@@ -22,4 +38,82 @@ pub enum ContractError {
 
     #[error("Address is not cw-20 contract")]
     NotCw20Address,
+
+    #[error("No funds provided")]
+    NoFundsProvided,
+
+    #[error("Fee reserve deposit must cover the denom creation fee")]
+    FeeReserveDepositTooLow,
+
+    #[error("Fee reserve not found")]
+    FeeReserveNotFound,
+
+    #[error("Unauthorized")]
+    Unauthorized,
+
+    #[error("Fee basis points out of allowed range")]
+    InvalidFeeBps,
+
+    #[error("No fees to claim")]
+    NoFeesToClaim,
+
+    #[error("No redemption claims")]
+    NoRedemptionClaims,
+
+    #[error("Minimum amount cannot be greater than maximum amount")]
+    InvalidTokenLimits,
+
+    #[error("Amount is below the minimum of {min}")]
+    AmountBelowMinimum { min: Uint128 },
+
+    #[error("Amount is above the maximum of {max}")]
+    AmountAboveMaximum { max: Uint128 },
+
+    #[error("Supply cap of {cap} would be exceeded")]
+    SupplyCapExceeded { cap: Uint128 },
+
+    #[error("Rate limit window must be non-empty and quotas cannot exceed 10000 basis points")]
+    InvalidRateLimit,
+
+    #[error("Wrap rate limit exceeded")]
+    InflowRateLimitExceeded,
+
+    #[error("Redemption rate limit exceeded")]
+    OutflowRateLimitExceeded,
+
+    #[error("Address {address} is blocked")]
+    AddressBlocked { address: String },
+
+    #[error("Address {address} is not allowlisted")]
+    AddressNotAllowlisted { address: String },
+
+    #[error("Token is frozen")]
+    TokenFrozen,
+
+    #[error("Token already has a manager")]
+    TokenManagerAlreadySet,
+
+    #[error("Creation of TF denom for {cw20} failed: {reason}")]
+    DenomCreationFailed { cw20: String, reason: String },
+
+    #[error("TF tokens of {cw20} minted before their denom was created")]
+    DenomNotCreated { cw20: String },
+
+    #[error("Minting TF tokens of {cw20} failed: {reason}")]
+    MintFailed { cw20: String, reason: String },
+
+    #[error("Releasing {cw20} tokens to {recipient} failed: {reason}")]
+    ReleaseFailed { cw20: String, recipient: String, reason: String },
+
+    #[error("Burning TF tokens of {cw20} failed: {reason}")]
+    BurnFailed { cw20: String, reason: String },
+
+    #[error("Unexpected reply {id}")]
+    UnexpectedReply { id: u64 },
+
+    #[error("Cannot migrate from contract {contract}")]
+    InvalidMigrationSource { contract: String },
+
+    #[error("Admin has to be provided when migrating an adapter without configuration")]
+    MigrationAdminRequired,
 }
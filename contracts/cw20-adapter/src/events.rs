@@ -0,0 +1,267 @@
+use cosmwasm_std::{Addr, Coin, Event, Uint128};
+
+use crate::state::{AutoRegistration, CodePin, Config, FeeSettings, MetadataOverride, RateLimit, Registration, TokenLimits};
+
+// Every action emits one event of its own type. Tokens are always identified by `cw20` (and `denom` where TF tokens
+// are involved), accounts by `sender` (caller) and `recipient`. Optional values are left out instead of being empty
+
+pub fn register_event(cw20: &Addr, denom: &str, registration: &Registration) -> Event {
+    let mut event = Event::new("register")
+        .add_attribute("cw20", cw20)
+        .add_attribute("denom", denom)
+        .add_attribute("registrant", &registration.registrant);
+    if !registration.fee_paid.is_empty() {
+        event = event.add_attribute("fee_paid", coins_to_string(&registration.fee_paid));
+    }
+    if let Some(sponsor) = &registration.sponsor {
+        event = event.add_attribute("sponsor", sponsor);
+    }
+    if registration.symbol_collision {
+        event = event.add_attribute("symbol_collision", "true");
+    }
+    if registration.name_collision {
+        event = event.add_attribute("name_collision", "true");
+    }
+    event
+}
+
+pub fn wrap_event(cw20: &Addr, denom: &str, sender: &str, recipient: &str, amount: Uint128, fee: Uint128) -> Event {
+    transfer_event("wrap", cw20, denom, sender, recipient, amount, fee)
+}
+
+pub fn redeem_event(cw20: &str, denom: &str, sender: &Addr, recipient: &str, amount: Uint128, fee: Uint128) -> Event {
+    transfer_event("redeem", cw20, denom, sender.as_str(), recipient, amount, fee)
+}
+
+fn transfer_event(ty: &str, cw20: impl Into<String>, denom: &str, sender: &str, recipient: &str, amount: Uint128, fee: Uint128) -> Event {
+    Event::new(ty)
+        .add_attribute("cw20", cw20)
+        .add_attribute("denom", denom)
+        .add_attribute("sender", sender)
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount)
+        .add_attribute("fee", fee)
+}
+
+/// Emitted instead of `wrap` when the CW-20 no longer runs its pinned code
+pub fn token_frozen_event(cw20: &Addr, pinned: &CodePin, current: &CodePin) -> Event {
+    Event::new("token_frozen")
+        .add_attribute("cw20", cw20)
+        .add_attribute("pinned_code_id", pinned.code_id.to_string())
+        .add_attribute("pinned_checksum", pinned.checksum.to_hex())
+        .add_attribute("code_id", current.code_id.to_string())
+        .add_attribute("checksum", current.checksum.to_hex())
+}
+
+pub fn update_metadata_event(cw20: &Addr, denom: &str) -> Event {
+    Event::new("update_metadata").add_attribute("cw20", cw20).add_attribute("denom", denom)
+}
+
+/// Summarises a `RefreshAllMetadata` page. Every refreshed token gets its own `update_metadata` event
+pub fn refresh_metadata_event(sender: &Addr, checked: usize, updated: usize, next_start_after: Option<&String>) -> Event {
+    let event = Event::new("refresh_metadata")
+        .add_attribute("sender", sender)
+        .add_attribute("checked", checked.to_string())
+        .add_attribute("updated", updated.to_string());
+    match next_start_after {
+        Some(next_start_after) => event.add_attribute("next_start_after", next_start_after),
+        None => event,
+    }
+}
+
+/// Summarises a `ProcessRedemptionClaims` page. Every paid claim gets its own `claim_redemption` event
+pub fn process_redemption_claims_event(sender: &Addr, cw20: &Addr, checked: usize, paid: usize, next_start_after: Option<&String>) -> Event {
+    let event = Event::new("process_redemption_claims")
+        .add_attribute("sender", sender)
+        .add_attribute("cw20", cw20)
+        .add_attribute("checked", checked.to_string())
+        .add_attribute("paid", paid.to_string());
+    match next_start_after {
+        Some(next_start_after) => event.add_attribute("next_start_after", next_start_after),
+        None => event,
+    }
+}
+
+pub fn deposit_fee_reserve_event(sender: &Addr, for_cw20: Option<&Addr>, funds: &[Coin]) -> Event {
+    fee_reserve_event("deposit_fee_reserve", sender, for_cw20, funds)
+}
+
+pub fn withdraw_fee_reserve_event(sender: &Addr, for_cw20: Option<&Addr>, funds: &[Coin]) -> Event {
+    fee_reserve_event("withdraw_fee_reserve", sender, for_cw20, funds)
+}
+
+fn fee_reserve_event(ty: &str, sender: &Addr, for_cw20: Option<&Addr>, funds: &[Coin]) -> Event {
+    let event = Event::new(ty)
+        .add_attribute("sender", sender)
+        .add_attribute("funds", coins_to_string(funds));
+    match for_cw20 {
+        Some(cw20) => event.add_attribute("cw20", cw20),
+        None => event,
+    }
+}
+
+/// `asset` is either a TF denom or a CW-20 address
+pub fn claim_fees_event(sender: &Addr, asset: &str, amount: Uint128) -> Event {
+    Event::new("claim_fees")
+        .add_attribute("sender", sender)
+        .add_attribute("asset", asset)
+        .add_attribute("amount", amount)
+}
+
+/// Emitted by the reply to a failed CW-20 transfer of a redemption that opted into claims
+pub fn defer_redemption_event(cw20: &Addr, claimant: &Addr, amount: Uint128, reason: &str) -> Event {
+    Event::new("defer_redemption")
+        .add_attribute("cw20", cw20)
+        .add_attribute("claimant", claimant)
+        .add_attribute("amount", amount)
+        .add_attribute("reason", reason)
+}
+
+/// `sender` differs from `claimant` when claims are processed by the admin
+pub fn claim_redemption_event(sender: &Addr, cw20: &Addr, claimant: &Addr, amount: Uint128) -> Event {
+    Event::new("claim_redemption")
+        .add_attribute("sender", sender)
+        .add_attribute("cw20", cw20)
+        .add_attribute("claimant", claimant)
+        .add_attribute("amount", amount)
+}
+
+/// Carries the whole configuration after the update
+pub fn update_config_event(sender: &Addr, config: &Config) -> Event {
+    let mut event = Event::new("update_config").add_attribute("sender", sender);
+    if let Some(admin) = &config.admin {
+        event = event.add_attribute("admin", admin);
+    }
+    if let Some(treasury) = &config.treasury {
+        event = event.add_attribute("treasury", treasury);
+    }
+    if !config.registration_surcharge.is_empty() {
+        event = event.add_attribute("registration_surcharge", coins_to_string(&config.registration_surcharge));
+    }
+    event = event
+        .add_attribute("wrap_fee_bps", config.default_fees.wrap_fee_bps.to_string())
+        .add_attribute("unwrap_fee_bps", config.default_fees.unwrap_fee_bps.to_string())
+        .add_attribute("registrant_fee_share_bps", config.registrant_fee_share_bps.to_string())
+        .add_attribute("allowlist_enabled", config.allowlist_enabled.to_string())
+        .add_attribute("metadata_refresh_blocks", config.metadata_refresh_blocks.to_string());
+    let auto_registration = match config.auto_registration {
+        AutoRegistration::Enabled => "enabled",
+        AutoRegistration::SponsoredOnly => "sponsored_only",
+        AutoRegistration::Disabled => "disabled",
+    };
+    event = event.add_attribute("auto_registration", auto_registration);
+    let default_limits = [
+        ("default_supply_cap", config.default_limits.supply_cap),
+        ("default_min_wrap", config.default_limits.min_wrap),
+        ("default_max_wrap", config.default_limits.max_wrap),
+        ("default_min_redeem", config.default_limits.min_redeem),
+        ("default_max_redeem", config.default_limits.max_redeem),
+    ];
+    default_limits
+        .into_iter()
+        .filter_map(|(key, limit)| limit.map(|limit| (key, limit)))
+        .fold(event, |event, (key, limit)| event.add_attribute(key, limit))
+}
+
+/// Fee attributes are left out when token fees are cleared
+pub fn set_token_fees_event(sender: &Addr, cw20: &Addr, fees: Option<&FeeSettings>) -> Event {
+    let event = admin_event("set_token_fees", sender, cw20);
+    match fees {
+        Some(fees) => event
+            .add_attribute("wrap_fee_bps", fees.wrap_fee_bps.to_string())
+            .add_attribute("unwrap_fee_bps", fees.unwrap_fee_bps.to_string()),
+        None => event,
+    }
+}
+
+pub fn set_token_limits_event(sender: &Addr, cw20: &Addr, limits: &TokenLimits) -> Event {
+    let limits = [
+        ("supply_cap", limits.supply_cap),
+        ("min_wrap", limits.min_wrap),
+        ("max_wrap", limits.max_wrap),
+        ("min_redeem", limits.min_redeem),
+        ("max_redeem", limits.max_redeem),
+    ];
+    limits
+        .into_iter()
+        .filter_map(|(key, limit)| limit.map(|limit| (key, limit)))
+        .fold(admin_event("set_token_limits", sender, cw20), |event, (key, limit)| {
+            event.add_attribute(key, limit)
+        })
+}
+
+/// Rate limit attributes are left out when the rate limit is removed
+pub fn set_rate_limit_event(sender: &Addr, cw20: &Addr, rate_limit: Option<&RateLimit>) -> Event {
+    let mut event = admin_event("set_rate_limit", sender, cw20);
+    if let Some(rate_limit) = rate_limit {
+        event = event.add_attribute("window_seconds", rate_limit.window_seconds.to_string());
+        if let Some(bps) = rate_limit.max_inflow_bps {
+            event = event.add_attribute("max_inflow_bps", bps.to_string());
+        }
+        if let Some(bps) = rate_limit.max_outflow_bps {
+            event = event.add_attribute("max_outflow_bps", bps.to_string());
+        }
+    }
+    event
+}
+
+pub fn set_token_frozen_event(sender: &Addr, cw20: &Addr, frozen: bool) -> Event {
+    admin_event("set_token_frozen", sender, cw20).add_attribute("frozen", frozen.to_string())
+}
+
+pub fn set_token_verified_event(sender: &Addr, cw20: &Addr, verified: bool) -> Event {
+    admin_event("set_token_verified", sender, cw20).add_attribute("verified", verified.to_string())
+}
+
+/// Override attributes are left out when the override is removed or doesn't replace the value
+pub fn set_metadata_override_event(sender: &Addr, cw20: &Addr, metadata_override: Option<&MetadataOverride>) -> Event {
+    let event = admin_event("set_metadata_override", sender, cw20);
+    match metadata_override {
+        Some(metadata_override) => [
+            ("name", &metadata_override.name),
+            ("symbol", &metadata_override.symbol),
+            ("description", &metadata_override.description),
+            ("uri", &metadata_override.uri),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.as_ref().map(|value| (key, value)))
+        .fold(event, |event, (key, value)| event.add_attribute(key, value)),
+        None => event,
+    }
+}
+
+/// `claimed_as` is `minter` or `admin`, the CW-20 role that entitled the sender to the claim
+pub fn claim_token_manager_event(sender: &Addr, cw20: &Addr, claimed_as: &str) -> Event {
+    Event::new("claim_token_manager")
+        .add_attribute("sender", sender)
+        .add_attribute("cw20", cw20)
+        .add_attribute("claimed_as", claimed_as)
+}
+
+/// Manager is left out when the role is revoked
+pub fn set_token_manager_event(sender: &Addr, cw20: &Addr, manager: Option<&Addr>) -> Event {
+    let event = admin_event("set_token_manager", sender, cw20);
+    match manager {
+        Some(manager) => event.add_attribute("manager", manager),
+        None => event,
+    }
+}
+
+pub fn set_send_hook_event(sender: &Addr, cw20: &Addr, enabled: bool) -> Event {
+    admin_event("set_send_hook", sender, cw20).add_attribute("enabled", enabled.to_string())
+}
+
+/// `list` is `blocklist` or `allowlist`, `change` is `add` or `remove`
+pub fn access_list_event(list: &str, change: &str, sender: &Addr, address: &Addr) -> Event {
+    Event::new(format!("{}_{}", list, change))
+        .add_attribute("sender", sender)
+        .add_attribute("address", address)
+}
+
+fn admin_event(ty: &str, sender: &Addr, cw20: &Addr) -> Event {
+    Event::new(ty).add_attribute("sender", sender).add_attribute("cw20", cw20)
+}
+
+fn coins_to_string(coins: &[Coin]) -> String {
+    coins.iter().map(Coin::to_string).collect::<Vec<String>>().join(",")
+}
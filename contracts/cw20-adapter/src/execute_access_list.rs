@@ -0,0 +1,94 @@
+use cosmwasm_std::{Addr, DepsMut, MessageInfo, Response, StdResult, Storage};
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
+
+use crate::common::{ensure_admin, load_config};
+use crate::error::ContractError;
+use crate::events::access_list_event;
+use crate::state::{ALLOWLIST, BLOCKLIST};
+
+#[derive(Clone, Copy)]
+enum AccessList {
+    Blocklist,
+    Allowlist,
+}
+
+impl AccessList {
+    fn name(self) -> &'static str {
+        match self {
+            AccessList::Blocklist => "blocklist",
+            AccessList::Allowlist => "allowlist",
+        }
+    }
+
+    fn insert(self, storage: &mut dyn Storage, address: &Addr) -> StdResult<bool> {
+        match self {
+            AccessList::Blocklist => BLOCKLIST.insert(storage, address),
+            AccessList::Allowlist => ALLOWLIST.insert(storage, address),
+        }
+    }
+
+    fn remove(self, storage: &mut dyn Storage, address: &Addr) -> StdResult<bool> {
+        match self {
+            AccessList::Blocklist => BLOCKLIST.remove(storage, address),
+            AccessList::Allowlist => ALLOWLIST.remove(storage, address),
+        }
+    }
+}
+
+pub fn handle_update_blocklist(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    update_list(deps, &info.sender, AccessList::Blocklist, add, remove)
+}
+
+pub fn handle_update_allowlist(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    update_list(deps, &info.sender, AccessList::Allowlist, add, remove)
+}
+
+/// Emits an event for every address that actually changed its membership
+fn update_list(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    sender: &Addr,
+    list: AccessList,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    let mut response = Response::new();
+    for address in add {
+        let address = deps.api.addr_validate(&address)?;
+        if list.insert(deps.storage, &address)? {
+            response = response.add_event(access_list_event(list.name(), "add", sender, &address));
+        }
+    }
+    for address in remove {
+        let address = deps.api.addr_validate(&address)?;
+        if list.remove(deps.storage, &address)? {
+            response = response.add_event(access_list_event(list.name(), "remove", sender, &address));
+        }
+    }
+    Ok(response)
+}
+
+pub fn ensure_address_allowed(storage: &dyn Storage, address: &Addr) -> Result<(), ContractError> {
+    if BLOCKLIST.contains(storage, address) {
+        return Err(ContractError::AddressBlocked {
+            address: address.to_string(),
+        });
+    }
+    if load_config(storage)?.allowlist_enabled && !ALLOWLIST.contains(storage, address) {
+        return Err(ContractError::AddressNotAllowlisted {
+            address: address.to_string(),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use cosmwasm_std::{Api, DepsMut, MessageInfo, Response};
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
+
+use crate::common::ensure_admin;
+use crate::error::ContractError;
+use crate::events::update_config_event;
+use crate::execute_fees::{ensure_valid_fee_settings, ensure_valid_share_bps};
+use crate::execute_limits::ensure_valid_token_limits;
+use crate::msg::ConfigUpdate;
+use crate::state::{Config, CONFIG};
+
+pub fn handle_update_config(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    update: ConfigUpdate,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    let mut config = ensure_admin(deps.storage, &info.sender)?;
+    apply_config_update(deps.api, &mut config, update)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_event(update_config_event(&info.sender, &config)))
+}
+
+/// Validates and applies provided values, leaving the rest of `config` untouched
+pub fn apply_config_update(api: &dyn Api, config: &mut Config, update: ConfigUpdate) -> Result<(), ContractError> {
+    if let Some(admin) = update.admin {
+        config.admin = Some(api.addr_validate(&admin)?);
+    }
+    if let Some(treasury) = update.treasury {
+        config.treasury = Some(api.addr_validate(&treasury)?);
+    }
+    if let Some(surcharge) = update.registration_surcharge {
+        config.registration_surcharge = surcharge.into_iter().filter(|c| !c.amount.is_zero()).collect();
+    }
+    if let Some(fees) = update.default_fees {
+        ensure_valid_fee_settings(&fees)?;
+        config.default_fees = fees;
+    }
+    if let Some(share_bps) = update.registrant_fee_share_bps {
+        ensure_valid_share_bps(share_bps)?;
+        config.registrant_fee_share_bps = share_bps;
+    }
+    if let Some(allowlist_enabled) = update.allowlist_enabled {
+        config.allowlist_enabled = allowlist_enabled;
+    }
+    if let Some(metadata_refresh_blocks) = update.metadata_refresh_blocks {
+        config.metadata_refresh_blocks = metadata_refresh_blocks;
+    }
+    if let Some(auto_registration) = update.auto_registration {
+        config.auto_registration = auto_registration;
+    }
+    if let Some(default_limits) = update.default_limits {
+        ensure_valid_token_limits(&default_limits)?;
+        config.default_limits = default_limits;
+    }
+    Ok(())
+}
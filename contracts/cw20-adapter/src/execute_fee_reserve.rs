@@ -0,0 +1,115 @@
+use cosmwasm_std::{Addr, BankMsg, Coin, DepsMut, Event, MessageInfo, Order, Response, StdResult, Storage};
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
+
+use crate::common::{add_coin, deduct_coins, funds_cover, query_denom_creation_fee};
+use crate::error::ContractError;
+use crate::events::{deposit_fee_reserve_event, withdraw_fee_reserve_event};
+use crate::state::{fee_reserves, FeeReserve, FEE_RESERVE_TOTALS};
+
+/// Each deposit has to cover the denom creation fee, so that reserves are not split into unusable dust
+pub fn handle_deposit_fee_reserve(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    for_cw20: Option<Addr>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    if info.funds.is_empty() {
+        return Err(ContractError::NoFundsProvided);
+    }
+    let creation_fee = query_denom_creation_fee(&deps.querier)?;
+    if !funds_cover(&info.funds, &creation_fee) {
+        return Err(ContractError::FeeReserveDepositTooLow);
+    }
+    let for_cw20 = for_cw20.map(|a| deps.api.addr_validate(a.as_str())).transpose()?;
+    let earmark = for_cw20.as_ref().map(Addr::to_string).unwrap_or_default();
+
+    let mut reserve = fee_reserves()
+        .may_load(deps.storage, (&info.sender, earmark.as_str()))?
+        .unwrap_or(FeeReserve {
+            sponsor: info.sender.clone(),
+            for_cw20,
+            funds: vec![],
+        });
+    for coin in &info.funds {
+        add_coin(&mut reserve.funds, coin);
+        FEE_RESERVE_TOTALS.update(deps.storage, &coin.denom, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default() + coin.amount)
+        })?;
+    }
+    fee_reserves().save(deps.storage, (&info.sender, earmark.as_str()), &reserve)?;
+
+    Ok(Response::new().add_event(deposit_fee_reserve_event(&info.sender, reserve.for_cw20.as_ref(), &info.funds)))
+}
+
+pub fn handle_withdraw_fee_reserve(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    for_cw20: Option<Addr>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    let earmark = for_cw20.map(|a| a.to_string()).unwrap_or_default();
+    let reserve = fee_reserves()
+        .may_load(deps.storage, (&info.sender, earmark.as_str()))?
+        .ok_or(ContractError::FeeReserveNotFound)?;
+
+    fee_reserves().remove(deps.storage, (&info.sender, earmark.as_str()))?;
+    release_reserved_totals(deps.storage, &reserve.funds)?;
+
+    Ok(Response::new()
+        .add_event(withdraw_fee_reserve_event(&info.sender, reserve.for_cw20.as_ref(), &reserve.funds))
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: reserve.funds,
+        }))
+}
+
+/// Takes `fee` out of the first sponsor reserve covering it, preferring reserves earmarked for `cw20_addr` over general
+/// ones. A reserve left unable to cover the fee is closed, so reserves that cannot pay only remain after the creation fee
+/// rose, and those are skipped. Returns the sponsor that paid together with its closed reserve, whose remaining funds
+/// have to be refunded, or None if no reserve can cover the fee
+pub fn draw_fee_from_reserves(storage: &mut dyn Storage, cw20_addr: &Addr, fee: &[Coin]) -> StdResult<Option<(Addr, Option<FeeReserve>)>> {
+    for earmark in [cw20_addr.to_string(), String::new()] {
+        let reserve = fee_reserves()
+            .idx
+            .earmark
+            .prefix(earmark)
+            .range(storage, None, None, Order::Ascending)
+            .map(|r| r.map(|(_, reserve)| reserve))
+            .find(|r| r.as_ref().map_or(true, |r| funds_cover(&r.funds, fee)))
+            .transpose()?;
+
+        if let Some(mut reserve) = reserve {
+            let key_earmark = reserve.earmark();
+            deduct_coins(&mut reserve.funds, fee);
+            release_reserved_totals(storage, fee)?;
+            if funds_cover(&reserve.funds, fee) {
+                fee_reserves().save(storage, (&reserve.sponsor, key_earmark.as_str()), &reserve)?;
+                return Ok(Some((reserve.sponsor, None)));
+            }
+            fee_reserves().remove(storage, (&reserve.sponsor, key_earmark.as_str()))?;
+            release_reserved_totals(storage, &reserve.funds)?;
+            return Ok(Some((reserve.sponsor.clone(), Some(reserve))));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the message refunding remaining funds of a reserve closed by `draw_fee_from_reserves`, if any are left
+pub fn refund_closed_reserve(reserve: FeeReserve) -> Option<(BankMsg, Event)> {
+    if reserve.funds.is_empty() {
+        return None;
+    }
+    let event = withdraw_fee_reserve_event(&reserve.sponsor, reserve.for_cw20.as_ref(), &reserve.funds);
+    let message = BankMsg::Send {
+        to_address: reserve.sponsor.to_string(),
+        amount: reserve.funds,
+    };
+    Some((message, event))
+}
+
+fn release_reserved_totals(storage: &mut dyn Storage, funds: &[Coin]) -> StdResult<()> {
+    for coin in funds {
+        FEE_RESERVE_TOTALS.update(storage, &coin.denom, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().saturating_sub(coin.amount))
+        })?;
+    }
+    Ok(())
+}
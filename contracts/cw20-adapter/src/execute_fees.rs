@@ -0,0 +1,118 @@
+use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, DepsMut, MessageInfo, Order, Response, StdResult, Storage, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
+
+use crate::common::{ensure_admin, load_config, AdapterDenom};
+use crate::error::ContractError;
+use crate::events::{claim_fees_event, set_token_fees_event};
+use crate::state::{registrations, FeeSettings, CW20_CONTRACTS, FEE_BALANCES, TOKEN_FEES};
+
+const BPS_DENOMINATOR: u16 = 10_000;
+/// Highest wrap or unwrap fee that can be configured (10%)
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+pub fn handle_set_token_fees(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    addr: Addr,
+    fees: Option<FeeSettings>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    if !CW20_CONTRACTS.contains(deps.storage, addr.as_str()) {
+        return Err(ContractError::ContractNotRegistered);
+    }
+    match &fees {
+        Some(fees) => {
+            ensure_valid_fee_settings(fees)?;
+            TOKEN_FEES.save(deps.storage, addr.as_str(), fees)?;
+        }
+        None => TOKEN_FEES.remove(deps.storage, addr.as_str()),
+    }
+    Ok(Response::new().add_event(set_token_fees_event(&info.sender, &addr, fees.as_ref())))
+}
+
+/// Sends all fees accrued by the sender - TF tokens via bank and CW-20 tokens via transfer
+pub fn handle_claim_fees(deps: DepsMut<InjectiveQueryWrapper>, info: MessageInfo) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    let balances = FEE_BALANCES
+        .prefix(&info.sender)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(String, Uint128)>>>()?;
+    if balances.is_empty() {
+        return Err(ContractError::NoFeesToClaim);
+    }
+
+    let mut response = Response::new();
+    for (asset, amount) in balances {
+        FEE_BALANCES.remove(deps.storage, (&info.sender, asset.as_str()));
+        response = response.add_event(claim_fees_event(&info.sender, &asset, amount));
+        response = if AdapterDenom::new(&asset).is_ok() {
+            response.add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: vec![Coin::new(amount.u128(), asset)],
+            })
+        } else {
+            response.add_message(WasmMsg::Execute {
+                contract_addr: asset,
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: info.sender.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            })
+        };
+    }
+    Ok(response)
+}
+
+pub fn ensure_valid_fee_settings(fees: &FeeSettings) -> Result<(), ContractError> {
+    if fees.wrap_fee_bps > MAX_FEE_BPS || fees.unwrap_fee_bps > MAX_FEE_BPS {
+        return Err(ContractError::InvalidFeeBps);
+    }
+    Ok(())
+}
+
+pub fn ensure_valid_share_bps(share_bps: u16) -> Result<(), ContractError> {
+    if share_bps > BPS_DENOMINATOR {
+        return Err(ContractError::InvalidFeeBps);
+    }
+    Ok(())
+}
+
+pub fn token_fee_settings(storage: &dyn Storage, cw20_addr: &str) -> StdResult<FeeSettings> {
+    match TOKEN_FEES.may_load(storage, cw20_addr)? {
+        Some(fees) => Ok(fees),
+        None => Ok(load_config(storage)?.default_fees),
+    }
+}
+
+/// Calculates fee on `amount` and its split between the token registrant and the treasury. The registrant share of an
+/// auto-registered token goes to the sponsor that paid its registration, as the first depositor paid nothing, or to the
+/// treasury if the adapter paid it. No fee is taken if neither of them is known
+pub fn calculate_fee(storage: &dyn Storage, cw20_addr: &str, amount: Uint128, fee_bps: u16) -> StdResult<(Uint128, Vec<(Addr, Uint128)>)> {
+    let config = load_config(storage)?;
+    let registrant = registrations()
+        .may_load(storage, cw20_addr)?
+        .and_then(|r| if r.auto_registered { r.sponsor } else { Some(r.registrant) });
+    let fee = amount.multiply_ratio(fee_bps, BPS_DENOMINATOR);
+
+    let shares = match (registrant, config.treasury) {
+        (Some(registrant), Some(treasury)) => {
+            let registrant_share = fee.multiply_ratio(config.registrant_fee_share_bps, BPS_DENOMINATOR);
+            vec![(registrant, registrant_share), (treasury, fee - registrant_share)]
+        }
+        (Some(beneficiary), None) | (None, Some(beneficiary)) => vec![(beneficiary, fee)],
+        (None, None) => vec![],
+    };
+    let shares: Vec<(Addr, Uint128)> = shares.into_iter().filter(|(_, share)| !share.is_zero()).collect();
+    let fee = shares.iter().fold(Uint128::zero(), |acc, (_, share)| acc + *share);
+    Ok((fee, shares))
+}
+
+pub fn credit_fees(storage: &mut dyn Storage, asset: &str, shares: &[(Addr, Uint128)]) -> StdResult<()> {
+    for (beneficiary, share) in shares {
+        FEE_BALANCES.update(storage, (beneficiary, asset), |balance| -> StdResult<_> {
+            Ok(balance.unwrap_or_default() + *share)
+        })?;
+    }
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use cosmwasm_std::{Addr, DepsMut, MessageInfo, Response, StdResult, Storage, Uint128};
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
+
+use crate::common::{ensure_admin_or_token_manager, load_config};
+use crate::error::ContractError;
+use crate::events::set_token_limits_event;
+use crate::state::{TokenLimits, CW20_CONTRACTS, TF_SUPPLY, TOKEN_LIMITS, TOTAL_TF_SUPPLY};
+
+/// The supply cap protects holders against a compromised minter, who is usually the token manager, so only the admin
+/// can change it. Token managers have to pass the cap currently in force
+pub fn handle_set_token_limits(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    addr: Addr,
+    limits: TokenLimits,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    ensure_admin_or_token_manager(deps.storage, &info.sender, addr.as_str())?;
+    if !CW20_CONTRACTS.contains(deps.storage, addr.as_str()) {
+        return Err(ContractError::ContractNotRegistered);
+    }
+    let is_admin = load_config(deps.storage)?.admin.as_ref() == Some(&info.sender);
+    if !is_admin && limits.supply_cap != token_limits(deps.storage, addr.as_str())?.supply_cap {
+        return Err(ContractError::Unauthorized);
+    }
+    save_token_limits(deps.storage, addr.as_str(), &limits)?;
+    Ok(Response::new().add_event(set_token_limits_event(&info.sender, &addr, &limits)))
+}
+
+pub fn save_token_limits(storage: &mut dyn Storage, cw20_addr: &str, limits: &TokenLimits) -> Result<(), ContractError> {
+    ensure_valid_token_limits(limits)?;
+    TOKEN_LIMITS.save(storage, cw20_addr, limits)?;
+    Ok(())
+}
+
+pub fn ensure_valid_token_limits(limits: &TokenLimits) -> Result<(), ContractError> {
+    ensure_min_not_above_max(limits.min_wrap, limits.max_wrap)?;
+    ensure_min_not_above_max(limits.min_redeem, limits.max_redeem)
+}
+
+/// Limits of a token replace `Config::default_limits` as a whole
+pub fn token_limits(storage: &dyn Storage, cw20_addr: &str) -> StdResult<TokenLimits> {
+    match TOKEN_LIMITS.may_load(storage, cw20_addr)? {
+        Some(limits) => Ok(limits),
+        None => Ok(load_config(storage)?.default_limits),
+    }
+}
+
+fn ensure_min_not_above_max(min: Option<Uint128>, max: Option<Uint128>) -> Result<(), ContractError> {
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            return Err(ContractError::InvalidTokenLimits);
+        }
+    }
+    Ok(())
+}
+
+fn ensure_within_bounds(amount: Uint128, min: Option<Uint128>, max: Option<Uint128>) -> Result<(), ContractError> {
+    if let Some(min) = min {
+        if amount < min {
+            return Err(ContractError::AmountBelowMinimum { min });
+        }
+    }
+    if let Some(max) = max {
+        if amount > max {
+            return Err(ContractError::AmountAboveMaximum { max });
+        }
+    }
+    Ok(())
+}
+
+pub fn ensure_wrap_within_limits(storage: &dyn Storage, cw20_addr: &str, amount: Uint128) -> Result<(), ContractError> {
+    let limits = token_limits(storage, cw20_addr)?;
+    ensure_within_bounds(amount, limits.min_wrap, limits.max_wrap)?;
+    if let Some(cap) = limits.supply_cap {
+        let supply = TF_SUPPLY.may_load(storage, cw20_addr)?.unwrap_or_default();
+        match supply.checked_add(amount) {
+            Ok(new_supply) if new_supply <= cap => {}
+            _ => return Err(ContractError::SupplyCapExceeded { cap }),
+        }
+    }
+    Ok(())
+}
+
+pub fn ensure_redeem_within_limits(storage: &dyn Storage, cw20_addr: &str, amount: Uint128) -> Result<(), ContractError> {
+    let limits = token_limits(storage, cw20_addr)?;
+    ensure_within_bounds(amount, limits.min_redeem, limits.max_redeem)
+}
+
+pub fn increase_supply(storage: &mut dyn Storage, height: u64, cw20_addr: &str, amount: Uint128) -> StdResult<Uint128> {
+    let total = TOTAL_TF_SUPPLY.may_load(storage)?.unwrap_or_default();
+    TOTAL_TF_SUPPLY.save(storage, &total.checked_add(amount)?)?;
+    TF_SUPPLY.update(storage, cw20_addr, height, |supply| -> StdResult<_> {
+        Ok(supply.unwrap_or_default().checked_add(amount)?)
+    })
+}
+
+/// Tokens registered before supply tracking was introduced may burn more than recorded, hence saturating
+pub fn decrease_supply(storage: &mut dyn Storage, height: u64, cw20_addr: &str, amount: Uint128) -> StdResult<Uint128> {
+    let supply = TF_SUPPLY.may_load(storage, cw20_addr)?.unwrap_or_default();
+    let new_supply = supply.saturating_sub(amount);
+    TF_SUPPLY.save(storage, cw20_addr, &new_supply, height)?;
+    let total = TOTAL_TF_SUPPLY.may_load(storage)?.unwrap_or_default();
+    TOTAL_TF_SUPPLY.save(storage, &total.saturating_sub(supply - new_supply))?;
+    Ok(new_supply)
+}
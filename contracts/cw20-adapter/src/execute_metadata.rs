@@ -1,10 +1,21 @@
-use cosmwasm_std::{Addr, DepsMut, Env, Response};
+use cosmwasm_std::{to_binary, Addr, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage};
+use cw20::{Cw20QueryMsg, DownloadLogoResponse, LogoInfo, MarketingInfoResponse, TokenInfoResponse};
+use cw_storage_plus::Bound;
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
 
-use injective_cosmwasm::{create_set_token_metadata_msg, InjectiveMsgWrapper, InjectiveQueryWrapper};
-
-use crate::common::{fetch_cw20_metadata, get_denom};
+use crate::common::{
+    create_set_denom_metadata_msg, ensure_admin_or_token_manager, fetch_cw20_metadata, get_denom, load_config, sync_registration_labels,
+};
 use crate::error::ContractError;
-use crate::state::CW20_CONTRACTS;
+use crate::events::{refresh_metadata_event, set_metadata_override_event, update_metadata_event};
+use crate::msg::RefreshMetadataResponse;
+use crate::state::{
+    DenomMetadata, DenomUnit, MetadataChange, MetadataOverride, MetadataSource, CW20_CONTRACTS, DENOM_METADATA, METADATA_HISTORY, METADATA_OVERRIDES,
+    METADATA_REFRESHED_AT,
+};
+
+const DEFAULT_REFRESH_LIMIT: u32 = 10;
+const MAX_REFRESH_LIMIT: u32 = 30;
 
 pub fn handle_update_metadata(
     deps: DepsMut<InjectiveQueryWrapper>,
@@ -15,10 +26,228 @@ pub fn handle_update_metadata(
     if !is_contract_registered {
         return Err(ContractError::ContractNotRegistered);
     }
-    let token_metadata = fetch_cw20_metadata(&deps, cw20_addr.as_str())?;
+    let token_info = fetch_cw20_metadata(&deps, cw20_addr.as_str())?;
+    sync_registration_labels(deps.storage, cw20_addr.as_str(), &token_info)?;
+    let denom = get_denom(&env.contract.address, &cw20_addr);
+    let metadata = build_denom_metadata(deps.as_ref(), &denom, &cw20_addr, token_info)?;
 
+    let event = update_metadata_event(&cw20_addr, &denom);
+    let set_metadata_message = save_and_set_denom_metadata(deps.storage, &env, cw20_addr.as_str(), &metadata)?;
+
+    Ok(Response::new().add_message(set_metadata_message).add_event(event))
+}
+
+/// Stores (or removes, if empty) the admin or token manager override of metadata of a CW-20 and sets the resulting metadata.
+/// Name and symbol overrides are admin only, as they would let a token manager take on the labels of another token
+pub fn handle_set_metadata_override(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    cw20_addr: Addr,
+    metadata_override: Option<MetadataOverride>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    ensure_admin_or_token_manager(deps.storage, &info.sender, cw20_addr.as_str())?;
+    if !CW20_CONTRACTS.contains(deps.storage, cw20_addr.as_str()) {
+        return Err(ContractError::ContractNotRegistered);
+    }
+    // an override without values replaces nothing, so it is removed rather than stored
+    let metadata_override = metadata_override.filter(|o| *o != MetadataOverride::default());
+    if load_config(deps.storage)?.admin.as_ref() != Some(&info.sender) {
+        let current = METADATA_OVERRIDES.may_load(deps.storage, cw20_addr.as_str())?.unwrap_or_default();
+        let new = metadata_override.clone().unwrap_or_default();
+        if new.name != current.name || new.symbol != current.symbol {
+            return Err(ContractError::Unauthorized);
+        }
+    }
+    match &metadata_override {
+        Some(metadata_override) => METADATA_OVERRIDES.save(deps.storage, cw20_addr.as_str(), metadata_override)?,
+        None => METADATA_OVERRIDES.remove(deps.storage, cw20_addr.as_str()),
+    }
+    let token_info = fetch_cw20_metadata(&deps, cw20_addr.as_str())?;
+    sync_registration_labels(deps.storage, cw20_addr.as_str(), &token_info)?;
     let denom = get_denom(&env.contract.address, &cw20_addr);
-    let set_metadata_message = create_set_token_metadata_msg(denom, token_metadata.name, token_metadata.symbol, token_metadata.decimals);
+    let metadata = build_denom_metadata(deps.as_ref(), &denom, &cw20_addr, token_info)?;
+    let set_metadata_message = save_and_set_denom_metadata(deps.storage, &env, cw20_addr.as_str(), &metadata)?;
+
+    Ok(Response::new()
+        .add_message(set_metadata_message)
+        .add_event(set_metadata_override_event(&info.sender, &cw20_addr, metadata_override.as_ref()))
+        .add_event(update_metadata_event(&cw20_addr, &denom)))
+}
+
+/// Sets metadata of up to `limit` registered CW-20s following `start_after`, skipping tokens whose metadata equals the
+/// one set last time and tokens that fail to answer `TokenInfo`. Response data is `RefreshMetadataResponse`
+pub fn handle_refresh_all_metadata(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_REFRESH_LIMIT).min(MAX_REFRESH_LIMIT) as usize;
+    let mut cw20s = CW20_CONTRACTS
+        .items(deps.storage, start_after.as_deref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<String>>>()?;
+    let next_start_after = if cw20s.len() > limit {
+        cw20s.truncate(limit);
+        cw20s.last().cloned()
+    } else {
+        None
+    };
+
+    let mut response = Response::new();
+    let mut updated = vec![];
+    for cw20 in &cw20s {
+        let cw20_addr = Addr::unchecked(cw20);
+        let token_info = match fetch_cw20_metadata(&deps, cw20) {
+            Ok(token_info) => token_info,
+            Err(_) => continue,
+        };
+        sync_registration_labels(deps.storage, cw20, &token_info)?;
+        let denom = get_denom(&env.contract.address, &cw20_addr);
+        let metadata = build_denom_metadata(deps.as_ref(), &denom, &cw20_addr, token_info)?;
+        if DENOM_METADATA.may_load(deps.storage, cw20)?.as_ref() == Some(&metadata) {
+            METADATA_REFRESHED_AT.save(deps.storage, cw20, &env.block.height)?;
+            continue;
+        }
+        response = response
+            .add_message(save_and_set_denom_metadata(deps.storage, &env, cw20, &metadata)?)
+            .add_event(update_metadata_event(&cw20_addr, &denom));
+        updated.push(cw20.clone());
+    }
+
+    let refreshed = RefreshMetadataResponse { updated, next_start_after };
+    Ok(response
+        .add_event(refresh_metadata_event(
+            &info.sender,
+            cw20s.len(),
+            refreshed.updated.len(),
+            refreshed.next_start_after.as_ref(),
+        ))
+        .set_data(to_binary(&refreshed)?))
+}
+
+/// Remembers `metadata` as set for the TF denom of `cw20_addr`, records it in history if it differs from the previous
+/// one and returns the message setting it
+pub fn save_and_set_denom_metadata(
+    storage: &mut dyn Storage,
+    env: &Env,
+    cw20_addr: &str,
+    metadata: &DenomMetadata,
+) -> StdResult<CosmosMsg<InjectiveMsgWrapper>> {
+    if DENOM_METADATA.may_load(storage, cw20_addr)?.as_ref() != Some(metadata) {
+        let seq = match METADATA_HISTORY
+            .prefix(cw20_addr)
+            .keys(storage, None, None, Order::Descending)
+            .next()
+            .transpose()?
+        {
+            Some(last_seq) => last_seq + 1,
+            None => 0,
+        };
+        let source = if METADATA_OVERRIDES.has(storage, cw20_addr) {
+            MetadataSource::Override
+        } else {
+            MetadataSource::Cw20
+        };
+        let change = MetadataChange {
+            seq,
+            height: env.block.height,
+            source,
+            metadata: metadata.clone(),
+        };
+        METADATA_HISTORY.save(storage, (cw20_addr, seq), &change)?;
+    }
+    DENOM_METADATA.save(storage, cw20_addr, metadata)?;
+    METADATA_REFRESHED_AT.save(storage, cw20_addr, &env.block.height)?;
+    Ok(create_set_denom_metadata_msg(&env.contract.address, metadata))
+}
+
+/// Re-fetches metadata of the TF denom of `cw20_addr` if it was last set at least `Config::metadata_refresh_blocks`
+/// ago (or never) and returns the message setting it if it changed. Name and symbol of the registration follow the CW-20.
+/// CW-20s failing to answer `TokenInfo` are left alone
+pub fn refresh_stale_metadata(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    env: &Env,
+    cw20_addr: &Addr,
+) -> Result<Option<CosmosMsg<InjectiveMsgWrapper>>, ContractError> {
+    let refresh_blocks = load_config(deps.storage)?.metadata_refresh_blocks;
+    if refresh_blocks == 0 {
+        return Ok(None);
+    }
+    if let Some(refreshed_at) = METADATA_REFRESHED_AT.may_load(deps.storage, cw20_addr.as_str())? {
+        if env.block.height < refreshed_at.saturating_add(refresh_blocks) {
+            return Ok(None);
+        }
+    }
+    let token_info = match fetch_cw20_metadata(&deps, cw20_addr.as_str()) {
+        Ok(token_info) => token_info,
+        Err(_) => return Ok(None),
+    };
+    sync_registration_labels(deps.storage, cw20_addr.as_str(), &token_info)?;
+    let denom = get_denom(&env.contract.address, cw20_addr);
+    let metadata = build_denom_metadata(deps.as_ref(), &denom, cw20_addr, token_info)?;
+    if DENOM_METADATA.may_load(deps.storage, cw20_addr.as_str())?.as_ref() == Some(&metadata) {
+        METADATA_REFRESHED_AT.save(deps.storage, cw20_addr.as_str(), &env.block.height)?;
+        return Ok(None);
+    }
+    Ok(Some(save_and_set_denom_metadata(deps.storage, env, cw20_addr.as_str(), &metadata)?))
+}
+
+/// Builds bank metadata of `denom` from CW-20 token info and, if the CW-20 implements marketing, its description and logo.
+/// The display unit is the CW-20 symbol with CW-20 decimals as exponent, or the base unit for tokens without decimals
+/// The admin override of the CW-20, if any, takes precedence over values coming from the CW-20
+pub fn build_denom_metadata(
+    deps: Deps<InjectiveQueryWrapper>,
+    denom: &str,
+    cw20_addr: &Addr,
+    mut token_info: TokenInfoResponse,
+) -> StdResult<DenomMetadata> {
+    let metadata_override = METADATA_OVERRIDES.may_load(deps.storage, cw20_addr.as_str())?.unwrap_or_default();
+    if let Some(name) = metadata_override.name {
+        token_info.name = name;
+    }
+    if let Some(symbol) = metadata_override.symbol {
+        token_info.symbol = symbol;
+    }
+    let querier = &deps.querier;
+    // marketing is an optional CW-20 extension, tokens without it just get no description and logo
+    let marketing: Option<MarketingInfoResponse> = querier.query_wasm_smart(cw20_addr, &Cw20QueryMsg::MarketingInfo {}).ok();
+    let (description, logo) = match marketing {
+        Some(marketing) => (marketing.description.unwrap_or_default(), marketing.logo),
+        None => (String::new(), None),
+    };
+    let uri = match logo {
+        Some(LogoInfo::Url(url)) => url,
+        Some(LogoInfo::Embedded) => querier
+            .query_wasm_smart::<DownloadLogoResponse>(cw20_addr, &Cw20QueryMsg::DownloadLogo {})
+            .map(|logo| format!("data:{};base64,{}", logo.mime_type, logo.data.to_base64()))
+            .unwrap_or_default(),
+        None => String::new(),
+    };
 
-    Ok(Response::new().add_message(set_metadata_message))
+    let mut denom_units = vec![DenomUnit {
+        denom: denom.to_string(),
+        exponent: 0,
+    }];
+    let display = if token_info.decimals > 0 {
+        denom_units.push(DenomUnit {
+            denom: token_info.symbol.clone(),
+            exponent: token_info.decimals.into(),
+        });
+        token_info.symbol.clone()
+    } else {
+        denom.to_string()
+    };
+    Ok(DenomMetadata {
+        description: metadata_override.description.unwrap_or(description),
+        denom_units,
+        base: denom.to_string(),
+        display,
+        name: token_info.name,
+        symbol: token_info.symbol,
+        uri: metadata_override.uri.unwrap_or(uri),
+        decimals: token_info.decimals,
+    })
 }
@@ -0,0 +1,106 @@
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Timestamp, Uint128};
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
+
+use crate::common::ensure_admin;
+use crate::error::ContractError;
+use crate::events::set_rate_limit_event;
+use crate::state::{RateLimit, RateLimitWindow, CW20_CONTRACTS, RATE_LIMITS, RATE_LIMIT_WINDOWS, TF_SUPPLY};
+
+const BPS_DENOMINATOR: u16 = 10_000;
+
+pub fn handle_set_rate_limit(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    addr: Addr,
+    rate_limit: Option<RateLimit>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    if !CW20_CONTRACTS.contains(deps.storage, addr.as_str()) {
+        return Err(ContractError::ContractNotRegistered);
+    }
+    match &rate_limit {
+        Some(rate_limit) => {
+            let invalid_bps = |bps: Option<u16>| matches!(bps, Some(bps) if bps > BPS_DENOMINATOR);
+            if rate_limit.window_seconds == 0 || invalid_bps(rate_limit.max_inflow_bps) || invalid_bps(rate_limit.max_outflow_bps) {
+                return Err(ContractError::InvalidRateLimit);
+            }
+            RATE_LIMITS.save(deps.storage, addr.as_str(), rate_limit)?;
+        }
+        None => RATE_LIMITS.remove(deps.storage, addr.as_str()),
+    }
+    // usage is counted from scratch under the new limit
+    RATE_LIMIT_WINDOWS.remove(deps.storage, addr.as_str());
+    Ok(Response::new().add_event(set_rate_limit_event(&info.sender, &addr, rate_limit.as_ref())))
+}
+
+/// Returns usage of the window `env.block.time` falls in, with windows aligned to multiples of `window_seconds`. Usage of
+/// the window before it is carried over, older usage is dropped
+pub fn current_window(storage: &dyn Storage, env: &Env, cw20_addr: &str, rate_limit: &RateLimit) -> StdResult<RateLimitWindow> {
+    let now = env.block.time.seconds();
+    let started_at = now - now % rate_limit.window_seconds;
+    let window = RATE_LIMIT_WINDOWS.may_load(storage, cw20_addr)?;
+    let (previous_inflow, previous_outflow) = match window {
+        Some(window) if window.started_at.seconds() >= started_at => return Ok(window),
+        Some(window) if window.started_at.seconds() >= started_at.saturating_sub(rate_limit.window_seconds) => (window.inflow, window.outflow),
+        _ => (Uint128::zero(), Uint128::zero()),
+    };
+    Ok(RateLimitWindow {
+        started_at: Timestamp::from_seconds(started_at),
+        supply_at_start: TF_SUPPLY.may_load(storage, cw20_addr)?.unwrap_or_default(),
+        inflow: Uint128::zero(),
+        outflow: Uint128::zero(),
+        previous_inflow,
+        previous_outflow,
+    })
+}
+
+/// Returns amounts wrapped and redeemed within the last `window_seconds`. Usage of the previous window is weighted by
+/// the part of it that still falls within that period
+pub fn rolling_flows(window: &RateLimitWindow, env: &Env, rate_limit: &RateLimit) -> (Uint128, Uint128) {
+    let elapsed = env.block.time.seconds().saturating_sub(window.started_at.seconds());
+    let overlap = rate_limit.window_seconds.saturating_sub(elapsed);
+    (
+        window.inflow + window.previous_inflow.multiply_ratio(overlap, rate_limit.window_seconds),
+        window.outflow + window.previous_outflow.multiply_ratio(overlap, rate_limit.window_seconds),
+    )
+}
+
+/// Quota is relative to the supply at window start, so it does not apply while nothing is locked
+fn is_within_quota(net_flow: Uint128, supply: Uint128, max_bps: Option<u16>) -> bool {
+    match max_bps {
+        Some(bps) if !supply.is_zero() => net_flow <= supply.multiply_ratio(bps, BPS_DENOMINATOR),
+        _ => true,
+    }
+}
+
+pub fn record_inflow(storage: &mut dyn Storage, env: &Env, cw20_addr: &str, amount: Uint128) -> Result<(), ContractError> {
+    let rate_limit = match RATE_LIMITS.may_load(storage, cw20_addr)? {
+        Some(rate_limit) => rate_limit,
+        None => return Ok(()),
+    };
+    let mut window = current_window(storage, env, cw20_addr, &rate_limit)?;
+    window.inflow += amount;
+
+    let (inflow, outflow) = rolling_flows(&window, env, &rate_limit);
+    if !is_within_quota(inflow.saturating_sub(outflow), window.supply_at_start, rate_limit.max_inflow_bps) {
+        return Err(ContractError::InflowRateLimitExceeded);
+    }
+    RATE_LIMIT_WINDOWS.save(storage, cw20_addr, &window)?;
+    Ok(())
+}
+
+pub fn record_outflow(storage: &mut dyn Storage, env: &Env, cw20_addr: &str, amount: Uint128) -> Result<(), ContractError> {
+    let rate_limit = match RATE_LIMITS.may_load(storage, cw20_addr)? {
+        Some(rate_limit) => rate_limit,
+        None => return Ok(()),
+    };
+    let mut window = current_window(storage, env, cw20_addr, &rate_limit)?;
+    window.outflow += amount;
+
+    let (inflow, outflow) = rolling_flows(&window, env, &rate_limit);
+    if !is_within_quota(outflow.saturating_sub(inflow), window.supply_at_start, rate_limit.max_outflow_bps) {
+        return Err(ContractError::OutflowRateLimitExceeded);
+    }
+    RATE_LIMIT_WINDOWS.save(storage, cw20_addr, &window)?;
+    Ok(())
+}
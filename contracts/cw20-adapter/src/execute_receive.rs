@@ -1,10 +1,26 @@
-use crate::common::{ensure_sufficient_create_denom_balance, get_denom, is_contract_registered, register_contract_and_get_message};
+use crate::common::{
+    ensure_sufficient_create_denom_balance, get_denom, is_contract_registered, load_config, query_denom_creation_fee,
+    register_contract_and_get_messages,
+};
 use crate::error::ContractError;
-use cosmwasm_std::{Coin, DepsMut, Env, MessageInfo, Response, Uint128};
+use crate::events::{register_event, token_frozen_event, update_metadata_event, wrap_event};
+use crate::execute_access_list::ensure_address_allowed;
+use crate::execute_fee_reserve::{draw_fee_from_reserves, refund_closed_reserve};
+use crate::execute_fees::{calculate_fee, credit_fees, token_fee_settings};
+use crate::execute_limits::{ensure_wrap_within_limits, increase_supply};
+use crate::execute_metadata::refresh_stale_metadata;
+use crate::execute_rate_limits::record_inflow;
+use crate::execute_token_restrictions::{ensure_token_not_frozen, verify_code_pin};
+use crate::operations::record_operation;
+use crate::reply::submsg_with_reply;
+use crate::state::{AutoRegistration, OperationKind, PendingReply, Registration};
+use crate::stats::record_wrap_stats;
+use cosmwasm_std::{to_binary, Coin, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
 use injective_cosmwasm::{create_mint_tokens_msg, InjectiveMsgWrapper, InjectiveQueryWrapper};
 
 pub fn handle_on_received_cw20_funds_msg(
-    deps: DepsMut<InjectiveQueryWrapper>,
+    mut deps: DepsMut<InjectiveQueryWrapper>,
     env: Env,
     info: MessageInfo,
     recipient: String,
@@ -13,17 +29,121 @@ pub fn handle_on_received_cw20_funds_msg(
     if !info.funds.is_empty() {
         return Err(ContractError::SuperfluousFundsProvided);
     }
+    let depositor = deps.api.addr_validate(&recipient)?;
+    ensure_address_allowed(deps.storage, &depositor)?;
+
     let mut response = Response::new();
     let token_contract = info.sender;
     if !is_contract_registered(&deps, &token_contract) {
-        ensure_sufficient_create_denom_balance(&deps, &env)?;
-        response = response.add_message(register_contract_and_get_message(deps, &env, &token_contract)?);
+        let auto_registration = load_config(deps.storage)?.auto_registration;
+        if auto_registration == AutoRegistration::Disabled {
+            return Err(ContractError::AutoRegistrationDisabled);
+        }
+        let creation_fee = query_denom_creation_fee(&deps.querier)?;
+        let sponsor = match draw_fee_from_reserves(deps.storage, &token_contract, &creation_fee)? {
+            Some((sponsor, closed_reserve)) => {
+                if let Some((refund_message, refund_event)) = closed_reserve.and_then(refund_closed_reserve) {
+                    response = response.add_message(refund_message).add_event(refund_event);
+                }
+                Some(sponsor)
+            }
+            None => None,
+        };
+        if sponsor.is_none() {
+            if auto_registration == AutoRegistration::SponsoredOnly {
+                return Err(ContractError::AutoRegistrationNotSponsored);
+            }
+            ensure_sufficient_create_denom_balance(&deps, &env)?;
+        }
+        let mut registration = Registration {
+            registrant: depositor.clone(),
+            sponsor,
+            auto_registered: true,
+            fee_paid: creation_fee,
+            registered_at_height: env.block.height,
+            name: String::new(),
+            symbol: String::new(),
+            symbol_collision: false,
+            name_collision: false,
+        };
+        record_operation(
+            deps.storage,
+            &env,
+            OperationKind::Register,
+            &token_contract,
+            Uint128::zero(),
+            &depositor,
+            None,
+        )?;
+        response = response
+            .add_submessages(register_contract_and_get_messages(
+                deps.branch(),
+                &env,
+                &token_contract,
+                &mut registration,
+            )?)
+            .add_event(register_event(
+                &token_contract,
+                &get_denom(&env.contract.address, &token_contract),
+                &registration,
+            ));
+    }
+    ensure_token_not_frozen(deps.storage, token_contract.as_str())?;
+    if let Some((pinned, current)) = verify_code_pin(deps.branch(), &token_contract)? {
+        // CW-20 was migrated to unreviewed code - nothing gets minted and the deposit is sent back. The new code may
+        // reject the transfer, so it leaves a redemption claim instead of reverting the freeze
+        let refund_message = WasmMsg::Execute {
+            contract_addr: token_contract.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+            funds: vec![],
+        };
+        let pending_refund = PendingReply::DeferrableRelease {
+            cw20: token_contract.clone(),
+            claimant: depositor,
+            amount,
+        };
+        return Ok(response
+            .add_submessage(submsg_with_reply(deps.storage, refund_message, pending_refund)?)
+            .add_event(token_frozen_event(&token_contract, &pinned, &current)));
     }
+    ensure_wrap_within_limits(deps.storage, token_contract.as_str(), amount)?;
+    record_inflow(deps.storage, &env, token_contract.as_str(), amount)?;
+    increase_supply(deps.storage, env.block.height, token_contract.as_str(), amount)?;
+    record_operation(
+        deps.storage,
+        &env,
+        OperationKind::Wrap,
+        &token_contract,
+        amount,
+        &depositor,
+        Some(&depositor),
+    )?;
+    let set_metadata_message = refresh_stale_metadata(deps.branch(), &env, &token_contract)?;
     let master = env.contract.address;
 
     let denom = get_denom(&master, &token_contract);
-    let coins_to_mint = Coin::new(amount.u128(), denom);
-    let mint_tf_tokens_message = create_mint_tokens_msg(master, coins_to_mint, recipient);
+    let fee_bps = token_fee_settings(deps.storage, token_contract.as_str())?.wrap_fee_bps;
+    let (fee, fee_shares) = calculate_fee(deps.storage, token_contract.as_str(), amount, fee_bps)?;
+    credit_fees(deps.storage, &denom, &fee_shares)?;
+    record_wrap_stats(deps.storage, token_contract.as_str(), &depositor, amount, fee)?;
+
+    let coins_to_mint = Coin::new((amount - fee).u128(), denom.clone());
+    response = response.add_event(wrap_event(&token_contract, &denom, &recipient, &recipient, amount, fee));
+    let mint_tf_tokens_message = create_mint_tokens_msg(master.clone(), coins_to_mint, recipient);
+    let pending_mint = PendingReply::Mint {
+        cw20: token_contract.clone(),
+    };
+    response = response.add_submessage(submsg_with_reply(deps.storage, mint_tf_tokens_message, pending_mint.clone())?);
 
-    Ok(response.add_message(mint_tf_tokens_message))
+    // fee is minted to the adapter itself and held there until claimed
+    if !fee.is_zero() {
+        let mint_fee_message = create_mint_tokens_msg(master.clone(), Coin::new(fee.u128(), denom.clone()), master.to_string());
+        response = response.add_submessage(submsg_with_reply(deps.storage, mint_fee_message, pending_mint)?);
+    }
+    if let Some(set_metadata_message) = set_metadata_message {
+        response = response
+            .add_message(set_metadata_message)
+            .add_event(update_metadata_event(&token_contract, &denom));
+    }
+    Ok(response)
 }
@@ -1,10 +1,17 @@
-use cosmwasm_std::{to_binary, Binary, DepsMut, Env, MessageInfo, Response, WasmMsg};
+use cosmwasm_std::{to_binary, Addr, Binary, DepsMut, Env, MessageInfo, Response, WasmMsg};
 use cw20::Cw20ExecuteMsg;
 use injective_cosmwasm::{create_burn_tokens_msg, InjectiveMsgWrapper, InjectiveQueryWrapper};
 
 use crate::common::{AdapterCoin, AdapterDenom};
 use crate::error::ContractError;
-use crate::state::CW20_CONTRACTS;
+use crate::events::redeem_event;
+use crate::execute_access_list::ensure_address_allowed;
+use crate::execute_fees::{calculate_fee, token_fee_settings};
+use crate::execute_limits::ensure_redeem_within_limits;
+use crate::execute_rate_limits::record_outflow;
+use crate::execute_token_restrictions::ensure_token_not_frozen;
+use crate::reply::submsg_with_reply;
+use crate::state::{PendingRedemption, PendingReply, CW20_CONTRACTS};
 
 pub fn handle_redeem_msg(
     deps: DepsMut<InjectiveQueryWrapper>,
@@ -12,8 +19,33 @@ pub fn handle_redeem_msg(
     info: MessageInfo,
     recipient: Option<String>,
     submessage: Option<Binary>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    redeem(deps, env, info, recipient, submessage, false)
+}
+
+/// Redeems like `handle_redeem_msg` with transfer, except that a failed transfer leaves a redemption claim of the
+/// recipient instead of failing the redemption
+pub fn handle_redeem_or_claim_msg(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    recipient: Option<String>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    redeem(deps, env, info, recipient, None, true)
+}
+
+fn redeem(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    recipient: Option<String>,
+    submessage: Option<Binary>,
+    claim_on_failure: bool,
 ) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
     let recipient = recipient.unwrap_or_else(|| info.sender.to_string());
+    ensure_address_allowed(deps.storage, &info.sender)?;
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    ensure_address_allowed(deps.storage, &recipient_addr)?;
 
     if info.funds.len() > 1 {
         return Err(ContractError::SuperfluousFundsProvided);
@@ -41,26 +73,65 @@ pub fn handle_redeem_msg(
         return Err(ContractError::NoRegisteredTokensProvided);
     }
 
+    ensure_token_not_frozen(deps.storage, &cw20_addr)?;
+    ensure_redeem_within_limits(deps.storage, &cw20_addr, tokens_to_exchange.amount)?;
+    record_outflow(deps.storage, &env, &cw20_addr, tokens_to_exchange.amount)?;
+
+    let fee_bps = token_fee_settings(deps.storage, &cw20_addr)?.unwrap_fee_bps;
+    let (fee, fee_shares) = calculate_fee(deps.storage, &cw20_addr, tokens_to_exchange.amount, fee_bps)?;
+    let amount_to_release = tokens_to_exchange.amount - fee;
+    // supply, stats, operation log and fees are only updated once the burn (the last step) succeeds
+    let redemption = PendingRedemption {
+        cw20: Addr::unchecked(&cw20_addr),
+        redeemer: info.sender.clone(),
+        recipient: recipient_addr.clone(),
+        amount: tokens_to_exchange.amount,
+        fee_shares,
+    };
+
+    let event = redeem_event(
+        &cw20_addr,
+        &tokens_to_exchange.denom.as_string(),
+        &info.sender,
+        &recipient,
+        tokens_to_exchange.amount,
+        fee,
+    );
     let burn_tf_tokens_message = create_burn_tokens_msg(env.contract.address, tokens_to_exchange.as_coin());
 
     let cw20_message: WasmMsg = match submessage {
         None => WasmMsg::Execute {
-            contract_addr: cw20_addr,
+            contract_addr: cw20_addr.clone(),
             msg: to_binary(&Cw20ExecuteMsg::Transfer {
                 recipient,
-                amount: tokens_to_exchange.amount,
+                amount: amount_to_release,
             })?,
             funds: vec![],
         },
         Some(msg) => WasmMsg::Execute {
-            contract_addr: cw20_addr,
+            contract_addr: cw20_addr.clone(),
             msg: to_binary(&Cw20ExecuteMsg::Send {
                 contract: recipient,
-                amount: tokens_to_exchange.amount,
+                amount: amount_to_release,
                 msg,
             })?,
             funds: vec![],
         },
     };
-    Ok(Response::new().add_message(cw20_message).add_message(burn_tf_tokens_message))
+    let pending_release = if claim_on_failure {
+        PendingReply::DeferrableRelease {
+            cw20: Addr::unchecked(&cw20_addr),
+            claimant: recipient_addr,
+            amount: amount_to_release,
+        }
+    } else {
+        PendingReply::Release {
+            cw20: Addr::unchecked(&cw20_addr),
+            recipient: recipient_addr,
+        }
+    };
+    Ok(Response::new()
+        .add_submessage(submsg_with_reply(deps.storage, cw20_message, pending_release)?)
+        .add_submessage(submsg_with_reply(deps.storage, burn_tf_tokens_message, PendingReply::Burn(redemption))?)
+        .add_event(event))
 }
@@ -0,0 +1,120 @@
+use cosmwasm_std::{to_binary, Addr, DepsMut, MessageInfo, Order, Response, StdResult, Storage, SubMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::Bound;
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
+
+use crate::common::ensure_admin;
+use crate::error::ContractError;
+use crate::events::{claim_redemption_event, process_redemption_claims_event};
+use crate::execute_access_list::ensure_address_allowed;
+use crate::execute_token_restrictions::ensure_token_not_frozen;
+use crate::msg::ProcessRedemptionClaimsResponse;
+use crate::reply::submsg_with_reply;
+use crate::state::{PendingReply, REDEMPTION_CLAIMS, REDEMPTION_CLAIM_TOTALS, TOTAL_REDEMPTION_CLAIMS};
+
+const DEFAULT_PROCESS_LIMIT: u32 = 10;
+const MAX_PROCESS_LIMIT: u32 = 30;
+
+/// Pays out the sender's claim. If the CW-20 still refuses the transfer the whole message fails and the claim is kept.
+/// Claimants blocked (or not allowlisted) since the claim was recorded are refused
+pub fn handle_claim_redemption(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    addr: Addr,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    ensure_token_not_frozen(deps.storage, addr.as_str())?;
+    ensure_address_allowed(deps.storage, &info.sender)?;
+    let amount = REDEMPTION_CLAIMS
+        .may_load(deps.storage, (addr.as_str(), &info.sender))?
+        .ok_or(ContractError::NoRedemptionClaims)?;
+    let transfer_message = pay_out_redemption_claim(deps.storage, &addr, &info.sender, amount)?;
+    Ok(Response::new()
+        .add_submessage(transfer_message)
+        .add_event(claim_redemption_event(&info.sender, &addr, &info.sender, amount)))
+}
+
+/// Pays out up to `limit` claims of `addr` following claimant `start_after`, in claimant order, so that a claim whose
+/// transfer keeps failing can be stepped over. Claims of blocked (or not allowlisted) claimants are skipped and kept.
+/// Response data is `ProcessRedemptionClaimsResponse`. Admin only
+pub fn handle_process_redemption_claims(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    addr: Addr,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    ensure_token_not_frozen(deps.storage, addr.as_str())?;
+    let start_after = start_after.map(|a| deps.api.addr_validate(&a)).transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_PROCESS_LIMIT).min(MAX_PROCESS_LIMIT) as usize;
+    let mut claims = REDEMPTION_CLAIMS
+        .prefix(addr.as_str())
+        .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<(Addr, Uint128)>>>()?;
+    if claims.is_empty() {
+        return Err(ContractError::NoRedemptionClaims);
+    }
+    let next_start_after = if claims.len() > limit {
+        claims.truncate(limit);
+        claims.last().map(|(claimant, _)| claimant.to_string())
+    } else {
+        None
+    };
+
+    let mut response = Response::new();
+    let mut paid = vec![];
+    for (claimant, amount) in &claims {
+        if ensure_address_allowed(deps.storage, claimant).is_err() {
+            continue;
+        }
+        response = response
+            .add_submessage(pay_out_redemption_claim(deps.storage, &addr, claimant, *amount)?)
+            .add_event(claim_redemption_event(&info.sender, &addr, claimant, *amount));
+        paid.push(claimant.clone());
+    }
+
+    let processed = ProcessRedemptionClaimsResponse { paid, next_start_after };
+    Ok(response
+        .add_event(process_redemption_claims_event(
+            &info.sender,
+            &addr,
+            claims.len(),
+            processed.paid.len(),
+            processed.next_start_after.as_ref(),
+        ))
+        .set_data(to_binary(&processed)?))
+}
+
+/// Adds `amount` of `cw20` to the claim of `claimant`
+pub fn record_redemption_claim(storage: &mut dyn Storage, cw20: &Addr, claimant: &Addr, amount: Uint128) -> StdResult<()> {
+    let claim = REDEMPTION_CLAIMS.may_load(storage, (cw20.as_str(), claimant))?.unwrap_or_default();
+    REDEMPTION_CLAIMS.save(storage, (cw20.as_str(), claimant), &(claim + amount))?;
+    let token_total = REDEMPTION_CLAIM_TOTALS.may_load(storage, cw20.as_str())?.unwrap_or_default();
+    REDEMPTION_CLAIM_TOTALS.save(storage, cw20.as_str(), &(token_total + amount))?;
+    let total = TOTAL_REDEMPTION_CLAIMS.may_load(storage)?.unwrap_or_default();
+    TOTAL_REDEMPTION_CLAIMS.save(storage, &(total + amount))
+}
+
+/// Removes the claim of `claimant` and returns the transfer paying it out, which fails with `ReleaseFailed` naming the
+/// claimant
+fn pay_out_redemption_claim(storage: &mut dyn Storage, cw20: &Addr, claimant: &Addr, amount: Uint128) -> StdResult<SubMsg<InjectiveMsgWrapper>> {
+    REDEMPTION_CLAIMS.remove(storage, (cw20.as_str(), claimant));
+    let token_total = REDEMPTION_CLAIM_TOTALS.may_load(storage, cw20.as_str())?.unwrap_or_default();
+    REDEMPTION_CLAIM_TOTALS.save(storage, cw20.as_str(), &token_total.saturating_sub(amount))?;
+    let total = TOTAL_REDEMPTION_CLAIMS.may_load(storage)?.unwrap_or_default();
+    TOTAL_REDEMPTION_CLAIMS.save(storage, &total.saturating_sub(amount))?;
+    let transfer_message = WasmMsg::Execute {
+        contract_addr: cw20.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: claimant.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    };
+    let pending_release = PendingReply::Release {
+        cw20: cw20.clone(),
+        recipient: claimant.clone(),
+    };
+    submsg_with_reply(storage, transfer_message, pending_release)
+}
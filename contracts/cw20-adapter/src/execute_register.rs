@@ -1,37 +1,154 @@
-use crate::common::{is_contract_registered, query_denom_creation_fee, register_contract_and_get_message};
+use crate::common::{
+    deduct_coins, funds_cover, get_denom, is_contract_registered, load_config, query_registration_fee, register_contract_and_get_messages,
+};
 use crate::error::ContractError;
-use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response};
+use crate::events::register_event;
+use crate::execute_limits::save_token_limits;
+use crate::operations::record_operation;
+use crate::state::{Config, OperationKind, Registration, TokenLimits};
+use cosmwasm_std::{Addr, BankMsg, Coin, DepsMut, Env, Event, MessageInfo, Response, StdError, StdResult, SubMsg, Uint128};
 use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
-use std::cmp::Ordering;
 
+/// Limits can be set at registration by the admin only, as registrants could otherwise restrict CW-20s they don't own
 pub fn handle_register_msg(
-    deps: DepsMut<InjectiveQueryWrapper>,
+    mut deps: DepsMut<InjectiveQueryWrapper>,
     env: Env,
     info: MessageInfo,
     addr: Addr,
+    limits: Option<TokenLimits>,
 ) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
     if is_contract_registered(&deps, &addr) {
         return Err(ContractError::ContractAlreadyRegistered);
     }
-    let required_funds = query_denom_creation_fee(&deps.querier)?;
-    if info.funds.len() > required_funds.len() {
-        return Err(ContractError::SuperfluousFundsProvided);
+    let config = load_config(deps.storage)?;
+    if limits.is_some() && config.admin.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+    let required_funds = query_registration_fee(&deps.querier, deps.storage)?;
+    let refund = take_registration_fee(&info.funds, &required_funds)?;
+
+    let (register_msgs, event) = register(deps.branch(), &env, &info.sender, &addr, required_funds)?;
+    if let Some(limits) = limits {
+        save_token_limits(deps.storage, addr.as_str(), &limits)?;
+    }
+
+    let response = Response::new().add_submessages(register_msgs).add_event(event);
+    Ok(add_fee_transfers(response, &info.sender, refund, &config, 1)?)
+}
+
+/// Registers all `addrs` or none of them. The fee is the one of `RegisterCw20Contract` paid once per contract. Duplicated
+/// and already registered addresses are all reported in a single error
+pub fn handle_register_batch_msg(
+    mut deps: DepsMut<InjectiveQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    addrs: Vec<Addr>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    if addrs.is_empty() {
+        return Err(ContractError::NoContractsProvided);
+    }
+    let mut duplicated = vec![];
+    let mut already_registered = vec![];
+    for (i, addr) in addrs.iter().enumerate() {
+        if addrs[..i].contains(addr) {
+            if !duplicated.contains(&addr.to_string()) {
+                duplicated.push(addr.to_string());
+            }
+        } else if is_contract_registered(&deps, addr) {
+            already_registered.push(addr.to_string());
+        }
     }
+    if !duplicated.is_empty() || !already_registered.is_empty() {
+        return Err(ContractError::BatchRegistrationRejected {
+            duplicated,
+            already_registered,
+        });
+    }
+
+    let fee_per_contract = query_registration_fee(&deps.querier, deps.storage)?;
+    let required_funds = multiply_coins(&fee_per_contract, addrs.len())?;
+    let refund = take_registration_fee(&info.funds, &required_funds)?;
+
+    let config = load_config(deps.storage)?;
+    let mut response = Response::new();
+    for addr in &addrs {
+        let (register_msgs, event) = register(deps.branch(), &env, &info.sender, addr, fee_per_contract.clone())?;
+        response = response.add_submessages(register_msgs).add_event(event);
+    }
+    Ok(add_fee_transfers(response, &info.sender, refund, &config, addrs.len())?)
+}
 
-    let mut provided_funds = info.funds.iter();
+/// Returns the overpayment in fee denoms, which is refunded to the caller. Funds in other denoms are rejected
+fn take_registration_fee(funds: &[Coin], required_funds: &[Coin]) -> Result<Vec<Coin>, ContractError> {
+    if !funds_cover(funds, required_funds) {
+        return Err(ContractError::NotEnoughBalanceToPayDenomCreationFee);
+    }
+    if funds.iter().any(|c| !required_funds.iter().any(|r| r.denom == c.denom)) {
+        return Err(ContractError::SuperfluousFundsProvided);
+    }
+    let mut refund = funds.to_vec();
+    deduct_coins(&mut refund, required_funds);
+    Ok(refund)
+}
 
-    for required_coin in &required_funds {
-        let pf = provided_funds
-            .find(|c| -> bool { c.denom == required_coin.denom })
-            .ok_or(ContractError::NotEnoughBalanceToPayDenomCreationFee)?;
+/// Records `registrant` as the one that paid `fee_paid` for `addr`. Returns messages creating the TF denom and the event
+fn register(
+    mut deps: DepsMut<InjectiveQueryWrapper>,
+    env: &Env,
+    registrant: &Addr,
+    addr: &Addr,
+    fee_paid: Vec<Coin>,
+) -> Result<(Vec<SubMsg<InjectiveMsgWrapper>>, Event), ContractError> {
+    let mut registration = Registration {
+        registrant: registrant.clone(),
+        sponsor: None,
+        auto_registered: false,
+        fee_paid,
+        registered_at_height: env.block.height,
+        name: String::new(),
+        symbol: String::new(),
+        symbol_collision: false,
+        name_collision: false,
+    };
+    let register_msgs = register_contract_and_get_messages(deps.branch(), env, addr, &mut registration)?;
+    record_operation(deps.storage, env, OperationKind::Register, addr, Uint128::zero(), registrant, None)?;
+    Ok((
+        register_msgs,
+        register_event(addr, &get_denom(&env.contract.address, addr), &registration),
+    ))
+}
 
-        match pf.amount.cmp(&required_coin.amount) {
-            Ordering::Greater => return Err(ContractError::SuperfluousFundsProvided),
-            Ordering::Less => return Err(ContractError::NotEnoughBalanceToPayDenomCreationFee),
-            Ordering::Equal => {}
+/// Refunds the overpayment and forwards the surcharge of `registered` registrations to the treasury
+fn add_fee_transfers(
+    mut response: Response<InjectiveMsgWrapper>,
+    sender: &Addr,
+    refund: Vec<Coin>,
+    config: &Config,
+    registered: usize,
+) -> StdResult<Response<InjectiveMsgWrapper>> {
+    if !refund.is_empty() {
+        response = response.add_message(BankMsg::Send {
+            to_address: sender.to_string(),
+            amount: refund,
+        });
+    }
+    if let Some(treasury) = &config.treasury {
+        if !config.registration_surcharge.is_empty() {
+            response = response.add_message(BankMsg::Send {
+                to_address: treasury.to_string(),
+                amount: multiply_coins(&config.registration_surcharge, registered)?,
+            });
         }
     }
+    Ok(response)
+}
 
-    let create_denom_msg = register_contract_and_get_message(deps, &env, &addr)?;
-    Ok(Response::new().add_message(create_denom_msg))
+fn multiply_coins(coins: &[Coin], times: usize) -> StdResult<Vec<Coin>> {
+    coins
+        .iter()
+        .map(|c| -> StdResult<Coin> {
+            let amount = c.amount.checked_mul(Uint128::from(times as u128)).map_err(StdError::from)?;
+            Ok(Coin::new(amount.u128(), &c.denom))
+        })
+        .collect()
 }
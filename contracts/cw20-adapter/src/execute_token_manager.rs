@@ -0,0 +1,62 @@
+use cosmwasm_std::{Addr, DepsMut, MessageInfo, Response};
+use cw20::{Cw20QueryMsg, MinterResponse};
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
+
+use crate::common::ensure_admin;
+use crate::error::ContractError;
+use crate::events::{claim_token_manager_event, set_token_manager_event};
+use crate::state::{CW20_CONTRACTS, TOKEN_MANAGERS};
+
+/// Makes the sender manager of a CW-20 if it is the CW-20's minter or wasm admin. A token that already has a manager
+/// cannot be claimed again until the adapter admin revokes the role
+pub fn handle_claim_token_manager(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    addr: Addr,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    if !CW20_CONTRACTS.contains(deps.storage, addr.as_str()) {
+        return Err(ContractError::ContractNotRegistered);
+    }
+    if TOKEN_MANAGERS.has(deps.storage, addr.as_str()) {
+        return Err(ContractError::TokenManagerAlreadySet);
+    }
+    let claimed_as = if is_cw20_minter(&deps, &addr, &info.sender) {
+        "minter"
+    } else if deps.querier.query_wasm_contract_info(&addr)?.admin.as_deref() == Some(info.sender.as_str()) {
+        "admin"
+    } else {
+        return Err(ContractError::Unauthorized);
+    };
+    TOKEN_MANAGERS.save(deps.storage, addr.as_str(), &info.sender)?;
+    Ok(Response::new().add_event(claim_token_manager_event(&info.sender, &addr, claimed_as)))
+}
+
+/// Hands the manager role of a CW-20 over to `manager` or revokes it if not set. Admin only
+pub fn handle_set_token_manager(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    addr: Addr,
+    manager: Option<String>,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    if !CW20_CONTRACTS.contains(deps.storage, addr.as_str()) {
+        return Err(ContractError::ContractNotRegistered);
+    }
+    let manager = manager.map(|manager| deps.api.addr_validate(&manager)).transpose()?;
+    match &manager {
+        Some(manager) => TOKEN_MANAGERS.save(deps.storage, addr.as_str(), manager)?,
+        None => TOKEN_MANAGERS.remove(deps.storage, addr.as_str()),
+    }
+    Ok(Response::new().add_event(set_token_manager_event(&info.sender, &addr, manager.as_ref())))
+}
+
+/// CW-20s without a minter (or failing to answer `Minter`) have none to claim the role
+fn is_cw20_minter(deps: &DepsMut<InjectiveQueryWrapper>, cw20_addr: &Addr, sender: &Addr) -> bool {
+    match deps
+        .querier
+        .query_wasm_smart::<Option<MinterResponse>>(cw20_addr, &Cw20QueryMsg::Minter {})
+    {
+        Ok(Some(minter)) => minter.minter == sender.as_str(),
+        _ => false,
+    }
+}
@@ -0,0 +1,140 @@
+use cosmwasm_std::{Addr, Coin, DepsMut, Env, MessageInfo, Response, Storage};
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
+
+use crate::common::{
+    create_set_before_send_hook_msg, ensure_admin, ensure_admin_or_token_manager, get_denom, load_config, query_code_pin, AdapterDenom,
+};
+use crate::error::ContractError;
+use crate::events::{set_send_hook_event, set_token_frozen_event, set_token_verified_event};
+use crate::state::{CodePin, ADMIN_FROZEN_TOKENS, BLOCKLIST, CODE_PINS, CW20_CONTRACTS, FROZEN_TOKENS, SEND_HOOK_TOKENS, VERIFIED_TOKENS};
+
+/// Unfreezing by the admin accepts the code the CW-20 is currently running, so it also ends review of a token frozen after
+/// migration. Token managers cannot end that review nor lift a freeze set by the admin, they can only unfreeze tokens they
+/// froze themselves that still run the pinned code
+pub fn handle_set_token_frozen(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    addr: Addr,
+    frozen: bool,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    ensure_admin_or_token_manager(deps.storage, &info.sender, addr.as_str())?;
+    if !CW20_CONTRACTS.contains(deps.storage, addr.as_str()) {
+        return Err(ContractError::ContractNotRegistered);
+    }
+    let is_admin = load_config(deps.storage)?.admin.as_ref() == Some(&info.sender);
+    if frozen {
+        FROZEN_TOKENS.insert(deps.storage, addr.as_str())?;
+        if is_admin {
+            ADMIN_FROZEN_TOKENS.insert(deps.storage, addr.as_str())?;
+        }
+    } else {
+        if !is_admin && ADMIN_FROZEN_TOKENS.contains(deps.storage, addr.as_str()) {
+            return Err(ContractError::Unauthorized);
+        }
+        let code_pin = query_code_pin(&deps.querier, &addr)?;
+        let pinned = CODE_PINS.may_load(deps.storage, addr.as_str())?;
+        if !is_admin && matches!(pinned, Some(pinned) if pinned != code_pin) {
+            return Err(ContractError::Unauthorized);
+        }
+        FROZEN_TOKENS.remove(deps.storage, addr.as_str())?;
+        ADMIN_FROZEN_TOKENS.remove(deps.storage, addr.as_str())?;
+        CODE_PINS.save(deps.storage, addr.as_str(), &code_pin)?;
+    }
+    Ok(Response::new().add_event(set_token_frozen_event(&info.sender, &addr, frozen)))
+}
+
+/// Marks a CW-20 as vouched for by the admin. Only informational, verified tokens are treated like any other
+pub fn handle_set_token_verified(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    info: MessageInfo,
+    addr: Addr,
+    verified: bool,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    if !CW20_CONTRACTS.contains(deps.storage, addr.as_str()) {
+        return Err(ContractError::ContractNotRegistered);
+    }
+    if verified {
+        VERIFIED_TOKENS.insert(deps.storage, addr.as_str())?;
+    } else {
+        VERIFIED_TOKENS.remove(deps.storage, addr.as_str())?;
+    }
+    Ok(Response::new().add_event(set_token_verified_event(&info.sender, &addr, verified)))
+}
+
+/// Opts the TF denom of a CW-20 in (or out of) transfer restrictions enforced by the adapter's before-send hook
+pub fn handle_set_send_hook(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    env: Env,
+    info: MessageInfo,
+    addr: Addr,
+    enabled: bool,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    ensure_admin(deps.storage, &info.sender)?;
+    if !CW20_CONTRACTS.contains(deps.storage, addr.as_str()) {
+        return Err(ContractError::ContractNotRegistered);
+    }
+    let denom = get_denom(&env.contract.address, &addr);
+    let hook_address = if enabled {
+        SEND_HOOK_TOKENS.insert(deps.storage, addr.as_str())?;
+        env.contract.address.to_string()
+    } else {
+        SEND_HOOK_TOKENS.remove(deps.storage, addr.as_str())?;
+        String::new()
+    };
+    Ok(Response::new()
+        .add_message(create_set_before_send_hook_msg(&env.contract.address, &denom, &hook_address))
+        .add_event(set_send_hook_event(&info.sender, &addr, enabled)))
+}
+
+/// Called by the bank module before every transfer of a TF denom that has the adapter set as hook.
+/// Mints and burns performed by the adapter itself are never blocked
+pub fn handle_block_before_send(
+    deps: DepsMut<InjectiveQueryWrapper>,
+    env: Env,
+    from: String,
+    to: String,
+    amount: Coin,
+) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    let denom = match AdapterDenom::new(&amount.denom) {
+        Ok(denom) => denom,
+        Err(_) => return Ok(Response::new()),
+    };
+    if !SEND_HOOK_TOKENS.contains(deps.storage, &denom.cw20_addr) {
+        return Ok(Response::new());
+    }
+    if from == env.contract.address.as_str() || to == env.contract.address.as_str() {
+        return Ok(Response::new());
+    }
+    ensure_token_not_frozen(deps.storage, &denom.cw20_addr)?;
+    for address in [from, to] {
+        if BLOCKLIST.contains(deps.storage, &Addr::unchecked(&address)) {
+            return Err(ContractError::AddressBlocked { address });
+        }
+    }
+    Ok(Response::new())
+}
+
+pub fn ensure_token_not_frozen(storage: &dyn Storage, cw20_addr: &str) -> Result<(), ContractError> {
+    if FROZEN_TOKENS.contains(storage, cw20_addr) {
+        return Err(ContractError::TokenFrozen);
+    }
+    Ok(())
+}
+
+/// Compares code running at `cw20_addr` with the pinned one. Tokens registered before pinning was introduced get pinned now.
+/// On a mismatch the token is frozen pending admin review and the pinned and current code are returned
+pub fn verify_code_pin(deps: DepsMut<InjectiveQueryWrapper>, cw20_addr: &Addr) -> Result<Option<(CodePin, CodePin)>, ContractError> {
+    let current = query_code_pin(&deps.querier, cw20_addr)?;
+    match CODE_PINS.may_load(deps.storage, cw20_addr.as_str())? {
+        Some(pinned) if pinned != current => {
+            FROZEN_TOKENS.insert(deps.storage, cw20_addr.as_str())?;
+            Ok(Some((pinned, current)))
+        }
+        Some(_) => Ok(None),
+        None => {
+            CODE_PINS.save(deps.storage, cw20_addr.as_str(), &current)?;
+            Ok(None)
+        }
+    }
+}
@@ -1,10 +1,24 @@
 pub mod common;
 pub mod contract;
 pub mod error;
+pub mod events;
+pub mod execute_access_list;
+pub mod execute_admin;
+pub mod execute_fee_reserve;
+pub mod execute_fees;
+pub mod execute_limits;
 pub mod execute_metadata;
+pub mod execute_rate_limits;
 pub mod execute_receive;
 pub mod execute_redeem;
+pub mod execute_redemption_claims;
 pub mod execute_register;
+pub mod execute_token_manager;
+pub mod execute_token_restrictions;
 pub mod msg;
+pub mod operations;
+pub mod proto;
 pub mod query;
+pub mod reply;
 pub mod state;
+pub mod stats;
@@ -1,32 +1,324 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Uint128};
 
+use crate::state::{AutoRegistration, FeeSettings, MetadataOverride, RateLimit, Registration, TokenLimits, TokenStats};
+
+/// All fields are optional, an empty message sets the instantiator as admin and leaves the rest of config at defaults
 #[cw_serde]
-pub struct InstantiateMsg {}
+#[derive(Default)]
+pub struct InstantiateMsg {
+    /// Initial configuration. Admin defaults to the instantiator
+    #[serde(default)]
+    pub config: ConfigUpdate,
+    /// CW-20 contracts registered at instantiation, as with `RegisterCw20Contracts`. Attached funds must cover their fees
+    #[serde(default)]
+    pub cw20s: Vec<Addr>,
+}
+
+/// Upgrades an adapter deployed before configuration was introduced, or one of a later version
+#[cw_serde]
+pub struct MigrateMsg {
+    /// New admin of the adapter. Required if the adapter has no configuration yet, otherwise the current admin is kept
+    pub admin: Option<String>,
+}
 
 #[cw_serde]
 pub struct ReceiveSubmsg {
     pub(crate) recipient: String,
 }
 
+/// Fields of adapter configuration to update. Missing fields are left unchanged
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    pub admin: Option<String>,
+    pub treasury: Option<String>,
+    pub registration_surcharge: Option<Vec<Coin>>,
+    pub default_fees: Option<FeeSettings>,
+    pub registrant_fee_share_bps: Option<u16>,
+    pub allowlist_enabled: Option<bool>,
+    pub metadata_refresh_blocks: Option<u64>,
+    pub auto_registration: Option<AutoRegistration>,
+    pub default_limits: Option<TokenLimits>,
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
-    /// Registers a new CW-20 contract that will be handled by the adapter
-    RegisterCw20Contract { addr: Addr },
+    /// Registers a new CW-20 contract that will be handled by the adapter. Funds above the registration fee are refunded.
+    /// Optional limits can be set for the new token by the admin only
+    RegisterCw20Contract { addr: Addr, limits: Option<TokenLimits> },
+    /// Registers all provided CW-20 contracts or none of them, paying the registration fee once per contract
+    RegisterCw20Contracts { addrs: Vec<Addr> },
     ///  Impl of Receiver CW-20 interface. Should be called by CW-20 contract only!! (never directly). Msg is ignored
     Receive { sender: String, amount: Uint128, msg: Binary },
     /// Called to redeem TF tokens. Will send CW-20 tokens to "recipient" address (or sender if not provided). Will use transfer method
     RedeemAndTransfer { recipient: Option<String> },
     /// Called to redeem TF tokens. Will call Send method of CW:20 to send CW-20 tokens to "recipient" address. Submessage will be passed to send method (can be empty)
     RedeemAndSend { recipient: String, submsg: Binary },
+    /// Same as RedeemAndTransfer, but if the CW-20 transfer fails the TF tokens are still burned and "recipient" gets a
+    /// redemption claim, paid out by ClaimRedemption or ProcessRedemptionClaims
+    RedeemAndTransferOrClaim { recipient: Option<String> },
+    /// Transfers CW-20 tokens of the sender's redemption claim of a CW-20
+    ClaimRedemption { addr: Addr },
+    /// Transfers CW-20 tokens of up to "limit" redemption claims of a CW-20 following claimant "start_after" to their
+    /// claimants. Admin only
+    ProcessRedemptionClaims {
+        addr: Addr,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Updates stored metadata
     UpdateMetadata { addr: Addr },
+    /// Updates metadata of a page of registered CW-20s, skipping those whose metadata did not change
+    RefreshAllMetadata { start_after: Option<String>, limit: Option<u32> },
+    /// Replaces metadata values coming from a CW-20 with provided ones (or removes the override if none are) and updates
+    /// metadata. Admin or token manager only
+    SetMetadataOverride {
+        addr: Addr,
+        metadata_override: Option<MetadataOverride>,
+    },
+    /// Prepays fees for auto-registration of CW-20 contracts. If "for_cw20" is provided, funds can only be used to register that contract
+    DepositFeeReserve { for_cw20: Option<Addr> },
+    /// Returns all unused funds from the sender's fee reserve (the one earmarked for "for_cw20" or the general one)
+    WithdrawFeeReserve { for_cw20: Option<Addr> },
+    /// Updates adapter configuration. Admin only
+    UpdateConfig(ConfigUpdate),
+    /// Overrides default wrap/unwrap fees for a single CW-20 (or restores defaults if "fees" are empty). Admin only
+    SetTokenFees { addr: Addr, fees: Option<FeeSettings> },
+    /// Sends all wrap/unwrap fees accrued by the sender
+    ClaimFees {},
+    /// Replaces supply cap and per-transaction limits of a CW-20. Admin or token manager only
+    SetTokenLimits { addr: Addr, limits: TokenLimits },
+    /// Sets or removes (if "rate_limit" is empty) wrap/redeem rate limit of a CW-20. Resets its usage. Admin only
+    SetRateLimit { addr: Addr, rate_limit: Option<RateLimit> },
+    /// Adds and removes addresses from the blocklist. Admin only
+    UpdateBlocklist { add: Vec<String>, remove: Vec<String> },
+    /// Adds and removes addresses from the allowlist (used only if allowlist is enabled in config). Admin only
+    UpdateAllowlist { add: Vec<String>, remove: Vec<String> },
+    /// Freezes or unfreezes a CW-20. Frozen tokens cannot be wrapped, redeemed or (with send hook enabled) transferred.
+    /// Admin or token manager only, but only the admin can unfreeze a token running code other than the pinned one
+    SetTokenFrozen { addr: Addr, frozen: bool },
+    /// Sets or clears the adapter as before-send hook of the TF denom of a CW-20. Admin only
+    SetSendHook { addr: Addr, enabled: bool },
+    /// Sets or clears the verified badge of a CW-20. Admin only
+    SetTokenVerified { addr: Addr, verified: bool },
+    /// Makes the sender manager of a CW-20, allowed to set its metadata override, freeze and limits. Sender must be the
+    /// CW-20 minter or wasm admin
+    ClaimTokenManager { addr: Addr },
+    /// Hands the manager role of a CW-20 over or revokes it (if `manager` is not set). Admin only
+    SetTokenManager { addr: Addr, manager: Option<String> },
+}
+
+#[cw_serde]
+pub enum SudoMsg {
+    /// Before-send hook of TokenFactory. Returning an error blocks the transfer
+    BlockBeforeSend { from: String, to: String, amount: Coin },
+    /// Also sent by TokenFactory before every transfer of a hooked TF denom. Ignored, since the adapter keeps no record of
+    /// transfers
+    TrackBeforeSend { from: String, to: String, amount: Coin },
 }
 
 #[cw_serde]
 pub enum QueryMsg {
     /// Return a list of registered CW-20 contracts
     RegisteredContracts {},
-    /// Returns a fee required to register a new token-factory denom
+    /// Returns CW-20 contracts that were registered at the start of block `height` (paginated by CW-20 address)
+    RegisteredContractsAtHeight {
+        height: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns adapter-wide activity counters and per-token breakdown (paginated by CW-20 address)
+    Stats { start_after: Option<String>, limit: Option<u32> },
+    /// Returns a fee required to register a new token-factory denom (including registration surcharge)
     NewDenomFee {},
+    /// Returns all fee reserves funded by the sponsor
+    FeeReserves { sponsor: String },
+    /// Returns adapter configuration
+    Config {},
+    /// Returns registration details of a CW-20 contract together with its verified badge
+    Registration { addr: String },
+    /// Returns CW-20 contracts registered by an address (paginated by CW-20 address)
+    RegistrationsByRegistrant {
+        registrant: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns CW-20 contracts with a symbol, ignoring case and surrounding whitespace (paginated by CW-20 address)
+    RegistrationsBySymbol {
+        symbol: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns CW-20 contracts verified by the admin (paginated by CW-20 address)
+    VerifiedTokens { start_after: Option<String>, limit: Option<u32> },
+    /// Returns CW-20 contracts with a name, ignoring case and surrounding whitespace (paginated by CW-20 address)
+    RegistrationsByName {
+        name: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns amount of TF tokens minted to the depositor and the fee for wrapping "amount" of CW-20 tokens
+    SimulateWrap { addr: String, amount: Uint128 },
+    /// Returns amount of CW-20 tokens released to the recipient and the fee for redeeming "amount" of TF tokens
+    SimulateRedeem { addr: String, amount: Uint128 },
+    /// Returns wrap/unwrap fees accrued by the beneficiary
+    FeeBalances { beneficiary: String },
+    /// Returns CW-20 amount owed to the claimant by failed transfers of redemptions of a CW-20
+    RedemptionClaim { addr: String, claimant: String },
+    /// Returns redemption claims of a CW-20 (paginated by claimant)
+    RedemptionClaims {
+        addr: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns supply cap and per-transaction limits of a CW-20
+    TokenLimits { addr: String },
+    /// Returns amount of outstanding TF tokens minted for a CW-20
+    Supply { addr: String },
+    /// Returns amount of outstanding TF tokens minted for a CW-20 at the start of block `height`
+    SupplyAtHeight { addr: String, height: u64 },
+    /// Returns rate limit of a CW-20 together with its usage over the last window
+    RateLimit { addr: String },
+    /// Returns blocked addresses
+    Blocklist { start_after: Option<String>, limit: Option<u32> },
+    /// Returns allowlisted addresses
+    Allowlist { start_after: Option<String>, limit: Option<u32> },
+    /// Returns frozen status and send hook status of a CW-20
+    TokenRestrictions { addr: String },
+    /// Returns code ID and checksum pinned for a CW-20
+    CodePin { addr: String },
+    /// Returns manager of a CW-20, if any
+    TokenManager { addr: String },
+    /// Returns metadata override of a CW-20 set by the admin
+    MetadataOverride { addr: String },
+    /// Returns metadata changes of a CW-20, oldest first (paginated by sequence number)
+    MetadataHistory {
+        addr: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns logged operations of a CW-20 (paginated by sequence number)
+    OperationsByToken {
+        addr: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns logged operations an address took part in (paginated by sequence number)
+    OperationsByAddress {
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Returns CW-20 and TF balances of an address for registered CW-20s (paginated by CW-20 address) and the cursor of
+    /// the next page
+    Portfolio {
+        address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        hide_zero: Option<bool>,
+    },
+}
+
+#[cw_serde]
+pub struct SimulationResponse {
+    pub amount_out: Uint128,
+    pub fee: Uint128,
+}
+
+#[cw_serde]
+pub struct RedemptionClaim {
+    pub claimant: Addr,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct FeeBalance {
+    /// TF denom or CW-20 address
+    pub asset: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct RateLimitResponse {
+    pub rate_limit: Option<RateLimit>,
+    pub supply_at_window_start: Uint128,
+    /// Amounts wrapped and redeemed within the last `window_seconds`, as counted against the quotas
+    pub inflow: Uint128,
+    pub outflow: Uint128,
+}
+
+#[cw_serde]
+pub struct TokenRestrictionsResponse {
+    pub frozen: bool,
+    pub send_hook_enabled: bool,
+}
+
+#[cw_serde]
+pub struct TokenStatsResponse {
+    pub cw20: String,
+    pub stats: TokenStats,
+    /// Outstanding TF supply, equal to the CW-20 amount backing it
+    pub locked: Uint128,
+    /// CW-20 amount owed to redemption claims, held by the adapter on top of the locked amount
+    pub claimed: Uint128,
+}
+
+#[cw_serde]
+pub struct StatsResponse {
+    /// Adapter-wide counters. Volumes, fees and locked amount are plain sums over all tokens
+    pub total: TokenStats,
+    pub total_locked: Uint128,
+    pub total_claimed: Uint128,
+    /// Per-token breakdown for the requested page of registered contracts
+    pub tokens: Vec<TokenStatsResponse>,
+}
+
+#[cw_serde]
+pub struct PortfolioEntry {
+    pub cw20: String,
+    pub denom: String,
+    /// Empty if the CW-20 failed to answer the balance query
+    pub cw20_balance: Option<Uint128>,
+    pub tf_balance: Uint128,
+}
+
+#[cw_serde]
+pub struct PortfolioResponse {
+    pub entries: Vec<PortfolioEntry>,
+    /// Pass as `start_after` to get the next page. Empty after the last page
+    pub next_start_after: Option<String>,
+}
+
+#[cw_serde]
+pub struct RegisteredContractsAtHeightResponse {
+    pub contracts: Vec<String>,
+    /// Pass as `start_after` to get the next page. Empty after the last page
+    pub next_start_after: Option<String>,
+}
+
+#[cw_serde]
+pub struct RegisteredToken {
+    pub cw20: String,
+    pub registration: Registration,
+    /// Set by the admin for tokens known to be genuine
+    pub verified: bool,
+}
+
+/// Data of the `ProcessRedemptionClaims` response
+#[cw_serde]
+pub struct ProcessRedemptionClaimsResponse {
+    /// Claimants whose claims were paid out, blocked (or not allowlisted) claimants are skipped
+    pub paid: Vec<Addr>,
+    /// Pass as `start_after` to process the next page. Empty after the last page
+    pub next_start_after: Option<String>,
+}
+
+/// Data of the `RefreshAllMetadata` response
+#[cw_serde]
+pub struct RefreshMetadataResponse {
+    /// CW-20s whose metadata was set
+    pub updated: Vec<String>,
+    /// Pass as `start_after` to refresh the next page. Empty after the last page
+    pub next_start_after: Option<String>,
 }
@@ -0,0 +1,56 @@
+use cosmwasm_std::{Addr, Empty, Env, StdResult, Storage, Uint128};
+
+use crate::state::{operations, Operation, OperationKind, ADDRESS_OPERATIONS, NEXT_OPERATION_SEQ};
+
+/// Number of most recent operations kept in the log
+pub const MAX_LOGGED_OPERATIONS: u64 = 10_000;
+
+/// Appends an operation to the log, dropping the oldest one once the log is full
+pub fn record_operation(
+    storage: &mut dyn Storage,
+    env: &Env,
+    kind: OperationKind,
+    cw20: &Addr,
+    amount: Uint128,
+    from: &Addr,
+    to: Option<&Addr>,
+) -> StdResult<u64> {
+    let seq = NEXT_OPERATION_SEQ.may_load(storage)?.unwrap_or_default();
+    NEXT_OPERATION_SEQ.save(storage, &(seq + 1))?;
+
+    let operation = Operation {
+        seq,
+        height: env.block.height,
+        kind,
+        cw20: cw20.clone(),
+        amount,
+        from: from.clone(),
+        to: to.cloned(),
+    };
+    for address in participants(&operation) {
+        ADDRESS_OPERATIONS.save(storage, (address, seq), &Empty {})?;
+    }
+    operations().save(storage, seq, &operation)?;
+
+    if seq >= MAX_LOGGED_OPERATIONS {
+        prune_operation(storage, seq - MAX_LOGGED_OPERATIONS)?;
+    }
+    Ok(seq)
+}
+
+fn prune_operation(storage: &mut dyn Storage, seq: u64) -> StdResult<()> {
+    if let Some(operation) = operations().may_load(storage, seq)? {
+        for address in participants(&operation) {
+            ADDRESS_OPERATIONS.remove(storage, (address, seq));
+        }
+        operations().remove(storage, seq)?;
+    }
+    Ok(())
+}
+
+fn participants(operation: &Operation) -> Vec<&Addr> {
+    match &operation.to {
+        Some(to) if *to != operation.from => vec![&operation.from, to],
+        _ => vec![&operation.from],
+    }
+}
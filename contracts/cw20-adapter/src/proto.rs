@@ -0,0 +1,67 @@
+//! Protobuf types of Injective messages not covered by `InjectiveMsg`, sent as Stargate messages. They follow the
+//! definitions in `injective/tokenfactory/v1beta1/tx.proto` and `cosmos/bank/v1beta1/bank.proto` of injective-core
+
+use cosmwasm_std::{Binary, CosmosMsg};
+use injective_cosmwasm::InjectiveMsgWrapper;
+use prost::Message;
+
+pub const MSG_SET_DENOM_METADATA_TYPE_URL: &str = "/injective.tokenfactory.v1beta1.MsgSetDenomMetadata";
+pub const MSG_SET_BEFORE_SEND_HOOK_TYPE_URL: &str = "/injective.tokenfactory.v1beta1.MsgSetBeforeSendHook";
+
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgSetDenomMetadata {
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    #[prost(message, optional, tag = "2")]
+    pub metadata: Option<Metadata>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgSetBeforeSendHook {
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    #[prost(string, tag = "2")]
+    pub denom: String,
+    #[prost(string, tag = "3")]
+    pub cosmwasm_address: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Metadata {
+    #[prost(string, tag = "1")]
+    pub description: String,
+    #[prost(message, repeated, tag = "2")]
+    pub denom_units: Vec<DenomUnit>,
+    #[prost(string, tag = "3")]
+    pub base: String,
+    #[prost(string, tag = "4")]
+    pub display: String,
+    #[prost(string, tag = "5")]
+    pub name: String,
+    #[prost(string, tag = "6")]
+    pub symbol: String,
+    #[prost(string, tag = "7")]
+    pub uri: String,
+    #[prost(string, tag = "8")]
+    pub uri_hash: String,
+    #[prost(uint32, tag = "9")]
+    pub decimals: u32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct DenomUnit {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+    #[prost(uint32, tag = "2")]
+    pub exponent: u32,
+    #[prost(string, repeated, tag = "3")]
+    pub aliases: Vec<String>,
+}
+
+/// Wraps encoded `msg` into a Stargate message of `type_url`
+pub fn stargate_msg(type_url: &str, msg: &impl Message) -> CosmosMsg<InjectiveMsgWrapper> {
+    CosmosMsg::Stargate {
+        type_url: type_url.to_string(),
+        value: Binary(msg.encode_to_vec()),
+    }
+}
@@ -1,8 +1,27 @@
-use crate::common::query_denom_creation_fee;
-use cosmwasm_std::{Coin, Deps, Order, StdResult};
+use crate::common::{get_denom, load_config, query_registration_fee};
+use cosmwasm_std::{Addr, Coin, Deps, Env, Order, StdResult, Uint128};
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use cw_storage_plus::Bound;
 use injective_cosmwasm::InjectiveQueryWrapper;
 
-use crate::state::CW20_CONTRACTS;
+use crate::execute_fees::{calculate_fee, token_fee_settings};
+use crate::execute_limits;
+use crate::execute_rate_limits::{current_window, rolling_flows};
+use crate::msg::{
+    FeeBalance, PortfolioEntry, PortfolioResponse, RateLimitResponse, RedemptionClaim, RegisteredContractsAtHeightResponse, RegisteredToken,
+    SimulationResponse, StatsResponse, TokenRestrictionsResponse, TokenStatsResponse,
+};
+use crate::state::{
+    fee_reserves, normalise_token_label, operations, registrations, CodePin, Config, FeeReserve, MetadataChange, MetadataOverride, Operation,
+    Registration, TokenLimits, ADAPTER_STATS, ADDRESS_OPERATIONS, ALLOWLIST, BLOCKLIST, CODE_PINS, CW20_CONTRACTS, FEE_BALANCES, FROZEN_TOKENS,
+    METADATA_HISTORY, METADATA_OVERRIDES, RATE_LIMITS, REDEMPTION_CLAIMS, REDEMPTION_CLAIM_TOTALS, REGISTRATION_STATUS, SEND_HOOK_TOKENS, TF_SUPPLY,
+    TOKEN_MANAGERS, TOKEN_STATS, TOTAL_REDEMPTION_CLAIMS, TOTAL_TF_SUPPLY, VERIFIED_TOKENS,
+};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+/// Registered CW-20s whose balance is checked by a single `Portfolio` query
+const MAX_PORTFOLIO_SCAN: usize = 30;
 
 pub fn registered_contracts(deps: Deps<InjectiveQueryWrapper>) -> StdResult<Vec<String>> {
     let contracts = CW20_CONTRACTS
@@ -12,6 +31,363 @@ pub fn registered_contracts(deps: Deps<InjectiveQueryWrapper>) -> StdResult<Vec<
     Ok(contracts)
 }
 
+/// Contracts registered before registration history was introduced are not reported
+/// Every contract with registration history counts towards `limit`, including those not registered at `height`
+pub fn registered_contracts_at_height(
+    deps: Deps<InjectiveQueryWrapper>,
+    height: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<RegisteredContractsAtHeightResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+    let keys = REGISTRATION_STATUS
+        .keys(deps.storage, start_after.as_deref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<String>>>()?;
+
+    let mut contracts = vec![];
+    for contract in keys.iter().take(limit) {
+        if REGISTRATION_STATUS.may_load_at_height(deps.storage, contract, height)? == Some(true) {
+            contracts.push(contract.clone());
+        }
+    }
+    Ok(RegisteredContractsAtHeightResponse {
+        contracts,
+        next_start_after: if keys.len() > limit { keys.get(limit - 1).cloned() } else { None },
+    })
+}
+
+pub fn stats(deps: Deps<InjectiveQueryWrapper>, start_after: Option<String>, limit: Option<u32>) -> StdResult<StatsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let tokens = CW20_CONTRACTS
+        .items(deps.storage, start_after.as_deref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|cw20| -> StdResult<TokenStatsResponse> {
+            let cw20 = cw20?;
+            Ok(TokenStatsResponse {
+                stats: TOKEN_STATS.may_load(deps.storage, &cw20)?.unwrap_or_default(),
+                locked: TF_SUPPLY.may_load(deps.storage, &cw20)?.unwrap_or_default(),
+                claimed: REDEMPTION_CLAIM_TOTALS.may_load(deps.storage, &cw20)?.unwrap_or_default(),
+                cw20,
+            })
+        })
+        .collect::<StdResult<Vec<TokenStatsResponse>>>()?;
+
+    Ok(StatsResponse {
+        total: ADAPTER_STATS.may_load(deps.storage)?.unwrap_or_default(),
+        total_locked: TOTAL_TF_SUPPLY.may_load(deps.storage)?.unwrap_or_default(),
+        total_claimed: TOTAL_REDEMPTION_CLAIMS.may_load(deps.storage)?.unwrap_or_default(),
+        tokens,
+    })
+}
+
 pub fn new_denom_fee(deps: Deps<InjectiveQueryWrapper>) -> StdResult<Vec<Coin>> {
-    query_denom_creation_fee(&deps.querier)
+    query_registration_fee(&deps.querier, deps.storage)
+}
+
+pub fn sponsor_fee_reserves(deps: Deps<InjectiveQueryWrapper>, sponsor: String) -> StdResult<Vec<FeeReserve>> {
+    let sponsor = deps.api.addr_validate(&sponsor)?;
+    fee_reserves()
+        .prefix(&sponsor)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|(_, reserve)| reserve))
+        .collect()
+}
+
+pub fn config(deps: Deps<InjectiveQueryWrapper>) -> StdResult<Config> {
+    load_config(deps.storage)
+}
+
+pub fn registration(deps: Deps<InjectiveQueryWrapper>, addr: String) -> StdResult<RegisteredToken> {
+    let registration = registrations().load(deps.storage, &addr)?;
+    Ok(registered_token(deps, addr, registration))
+}
+
+pub fn registrations_by_registrant(
+    deps: Deps<InjectiveQueryWrapper>,
+    registrant: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RegisteredToken>> {
+    let registrant = deps.api.addr_validate(&registrant)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    registrations()
+        .idx
+        .registrant
+        .prefix(registrant)
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|r| r.map(|(cw20, registration)| registered_token(deps, cw20, registration)))
+        .collect()
+}
+
+pub fn registrations_by_symbol(
+    deps: Deps<InjectiveQueryWrapper>,
+    symbol: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RegisteredToken>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    registrations()
+        .idx
+        .symbol
+        .prefix(normalise_token_label(&symbol))
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|r| r.map(|(cw20, registration)| registered_token(deps, cw20, registration)))
+        .collect()
+}
+
+pub fn registrations_by_name(
+    deps: Deps<InjectiveQueryWrapper>,
+    name: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RegisteredToken>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    registrations()
+        .idx
+        .name
+        .prefix(normalise_token_label(&name))
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|r| r.map(|(cw20, registration)| registered_token(deps, cw20, registration)))
+        .collect()
+}
+
+pub fn verified_tokens(deps: Deps<InjectiveQueryWrapper>, start_after: Option<String>, limit: Option<u32>) -> StdResult<Vec<String>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    VERIFIED_TOKENS
+        .items(deps.storage, start_after.as_deref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+fn registered_token(deps: Deps<InjectiveQueryWrapper>, cw20: String, registration: Registration) -> RegisteredToken {
+    RegisteredToken {
+        verified: VERIFIED_TOKENS.contains(deps.storage, &cw20),
+        cw20,
+        registration,
+    }
+}
+
+pub fn simulate_wrap(deps: Deps<InjectiveQueryWrapper>, addr: String, amount: Uint128) -> StdResult<SimulationResponse> {
+    let fee_bps = token_fee_settings(deps.storage, &addr)?.wrap_fee_bps;
+    let (fee, _) = calculate_fee(deps.storage, &addr, amount, fee_bps)?;
+    Ok(SimulationResponse {
+        amount_out: amount - fee,
+        fee,
+    })
+}
+
+pub fn simulate_redeem(deps: Deps<InjectiveQueryWrapper>, addr: String, amount: Uint128) -> StdResult<SimulationResponse> {
+    let fee_bps = token_fee_settings(deps.storage, &addr)?.unwrap_fee_bps;
+    let (fee, _) = calculate_fee(deps.storage, &addr, amount, fee_bps)?;
+    Ok(SimulationResponse {
+        amount_out: amount - fee,
+        fee,
+    })
+}
+
+pub fn fee_balances(deps: Deps<InjectiveQueryWrapper>, beneficiary: String) -> StdResult<Vec<FeeBalance>> {
+    let beneficiary = deps.api.addr_validate(&beneficiary)?;
+    FEE_BALANCES
+        .prefix(&beneficiary)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|(asset, amount)| FeeBalance { asset, amount }))
+        .collect()
+}
+
+pub fn redemption_claim(deps: Deps<InjectiveQueryWrapper>, addr: String, claimant: String) -> StdResult<Uint128> {
+    let claimant = deps.api.addr_validate(&claimant)?;
+    Ok(REDEMPTION_CLAIMS.may_load(deps.storage, (&addr, &claimant))?.unwrap_or_default())
+}
+
+pub fn redemption_claims(
+    deps: Deps<InjectiveQueryWrapper>,
+    addr: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<RedemptionClaim>> {
+    let start_after = start_after.map(|a| deps.api.addr_validate(&a)).transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    REDEMPTION_CLAIMS
+        .prefix(&addr)
+        .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|r| r.map(|(claimant, amount)| RedemptionClaim { claimant, amount }))
+        .collect()
+}
+
+pub fn token_limits(deps: Deps<InjectiveQueryWrapper>, addr: String) -> StdResult<TokenLimits> {
+    execute_limits::token_limits(deps.storage, &addr)
+}
+
+pub fn supply(deps: Deps<InjectiveQueryWrapper>, addr: String) -> StdResult<Uint128> {
+    Ok(TF_SUPPLY.may_load(deps.storage, &addr)?.unwrap_or_default())
+}
+
+pub fn supply_at_height(deps: Deps<InjectiveQueryWrapper>, addr: String, height: u64) -> StdResult<Uint128> {
+    Ok(TF_SUPPLY.may_load_at_height(deps.storage, &addr, height)?.unwrap_or_default())
+}
+
+pub fn rate_limit(deps: Deps<InjectiveQueryWrapper>, env: Env, addr: String) -> StdResult<RateLimitResponse> {
+    let rate_limit = match RATE_LIMITS.may_load(deps.storage, &addr)? {
+        Some(rate_limit) => rate_limit,
+        None => {
+            return Ok(RateLimitResponse {
+                rate_limit: None,
+                supply_at_window_start: Uint128::zero(),
+                inflow: Uint128::zero(),
+                outflow: Uint128::zero(),
+            })
+        }
+    };
+    let window = current_window(deps.storage, &env, &addr, &rate_limit)?;
+    let (inflow, outflow) = rolling_flows(&window, &env, &rate_limit);
+    Ok(RateLimitResponse {
+        rate_limit: Some(rate_limit),
+        supply_at_window_start: window.supply_at_start,
+        inflow,
+        outflow,
+    })
+}
+
+pub fn blocklist(deps: Deps<InjectiveQueryWrapper>, start_after: Option<String>, limit: Option<u32>) -> StdResult<Vec<Addr>> {
+    let start_after = start_after.map(|a| deps.api.addr_validate(&a)).transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    BLOCKLIST
+        .items(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn allowlist(deps: Deps<InjectiveQueryWrapper>, start_after: Option<String>, limit: Option<u32>) -> StdResult<Vec<Addr>> {
+    let start_after = start_after.map(|a| deps.api.addr_validate(&a)).transpose()?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    ALLOWLIST
+        .items(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
+
+pub fn token_restrictions(deps: Deps<InjectiveQueryWrapper>, addr: String) -> StdResult<TokenRestrictionsResponse> {
+    Ok(TokenRestrictionsResponse {
+        frozen: FROZEN_TOKENS.contains(deps.storage, &addr),
+        send_hook_enabled: SEND_HOOK_TOKENS.contains(deps.storage, &addr),
+    })
+}
+
+pub fn code_pin(deps: Deps<InjectiveQueryWrapper>, addr: String) -> StdResult<Option<CodePin>> {
+    CODE_PINS.may_load(deps.storage, &addr)
+}
+
+pub fn token_manager(deps: Deps<InjectiveQueryWrapper>, addr: String) -> StdResult<Option<Addr>> {
+    TOKEN_MANAGERS.may_load(deps.storage, &addr)
+}
+
+pub fn metadata_override(deps: Deps<InjectiveQueryWrapper>, addr: String) -> StdResult<Option<MetadataOverride>> {
+    METADATA_OVERRIDES.may_load(deps.storage, &addr)
+}
+
+pub fn metadata_history(
+    deps: Deps<InjectiveQueryWrapper>,
+    addr: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<MetadataChange>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    METADATA_HISTORY
+        .prefix(&addr)
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|r| r.map(|(_, change)| change))
+        .collect()
+}
+
+pub fn operations_by_token(
+    deps: Deps<InjectiveQueryWrapper>,
+    addr: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Operation>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    operations()
+        .idx
+        .cw20
+        .prefix(addr)
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|r| r.map(|(_, operation)| operation))
+        .collect()
+}
+
+pub fn operations_by_address(
+    deps: Deps<InjectiveQueryWrapper>,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Operation>> {
+    let address = deps.api.addr_validate(&address)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    ADDRESS_OPERATIONS
+        .prefix(&address)
+        .keys(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|seq| operations().load(deps.storage, seq?))
+        .collect()
+}
+
+/// With `hide_zero` tokens without any balance are skipped and do not count towards `limit`. At most
+/// `MAX_PORTFOLIO_SCAN` tokens are checked per call, so a page can be short even if more tokens hold balances
+pub fn portfolio(
+    deps: Deps<InjectiveQueryWrapper>,
+    env: Env,
+    address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    hide_zero: Option<bool>,
+) -> StdResult<PortfolioResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    // an empty page would echo `start_after` back as the cursor
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+    let hide_zero = hide_zero.unwrap_or(false);
+    // without `hide_zero` every checked token is returned
+    let scan_limit = if hide_zero { MAX_PORTFOLIO_SCAN } else { limit };
+    let cw20s = CW20_CONTRACTS
+        .items(deps.storage, start_after.as_deref().map(Bound::exclusive), None, Order::Ascending)
+        .take(scan_limit + 1)
+        .collect::<StdResult<Vec<String>>>()?;
+
+    let mut entries = vec![];
+    let mut next_start_after = None;
+    for (i, cw20) in cw20s.iter().enumerate() {
+        if entries.len() == limit || i == scan_limit {
+            next_start_after = cw20s[..i].last().cloned().or(start_after);
+            break;
+        }
+        let denom = get_denom(&env.contract.address, &Addr::unchecked(cw20));
+        // a paused or broken CW-20 must not make the whole page fail
+        let cw20_balance = deps
+            .querier
+            .query_wasm_smart::<BalanceResponse>(
+                cw20,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .ok()
+            .map(|r| r.balance);
+        let tf_balance = deps.querier.query_balance(&address, &denom)?.amount;
+        if hide_zero && cw20_balance == Some(Uint128::zero()) && tf_balance.is_zero() {
+            continue;
+        }
+        entries.push(PortfolioEntry {
+            cw20: cw20.clone(),
+            denom,
+            cw20_balance,
+            tf_balance,
+        });
+    }
+    Ok(PortfolioResponse { entries, next_start_after })
 }
@@ -0,0 +1,93 @@
+use cosmwasm_std::{CosmosMsg, DepsMut, Env, Reply, Response, StdResult, Storage, SubMsg, Uint128};
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
+
+use crate::error::ContractError;
+use crate::events::defer_redemption_event;
+use crate::execute_fees::credit_fees;
+use crate::execute_limits::decrease_supply;
+use crate::execute_redemption_claims::record_redemption_claim;
+use crate::operations::record_operation;
+use crate::state::{OperationKind, PendingRedemption, PendingReply, CW20_CONTRACTS, NEXT_REPLY_ID, PENDING_REPLIES, REGISTRATION_STATUS};
+use crate::stats::record_redeem_stats;
+
+/// Wraps `msg` into a submessage whose outcome is handled by `handle_reply` with `pending` as context
+pub fn submsg_with_reply(
+    storage: &mut dyn Storage,
+    msg: impl Into<CosmosMsg<InjectiveMsgWrapper>>,
+    pending: PendingReply,
+) -> StdResult<SubMsg<InjectiveMsgWrapper>> {
+    let id = NEXT_REPLY_ID.may_load(storage)?.unwrap_or_default();
+    NEXT_REPLY_ID.save(storage, &(id + 1))?;
+    PENDING_REPLIES.save(storage, id, &pending)?;
+    Ok(SubMsg::reply_always(msg, id))
+}
+
+/// Commits the effects of a successful step. A failed step fails the whole transaction with an error naming the step,
+/// except for transfers of redemptions that opted into claims, which leave a claim instead
+pub fn handle_reply(deps: DepsMut<InjectiveQueryWrapper>, env: Env, msg: Reply) -> Result<Response<InjectiveMsgWrapper>, ContractError> {
+    let pending = match PENDING_REPLIES.may_load(deps.storage, msg.id)? {
+        Some(pending) => pending,
+        None => return Err(ContractError::UnexpectedReply { id: msg.id }),
+    };
+    PENDING_REPLIES.remove(deps.storage, msg.id);
+    let result = msg.result.into_result();
+    match pending {
+        PendingReply::CreateDenom { cw20 } => {
+            result.map_err(|reason| ContractError::DenomCreationFailed {
+                cw20: cw20.to_string(),
+                reason,
+            })?;
+            CW20_CONTRACTS.insert(deps.storage, cw20.as_str())?;
+            REGISTRATION_STATUS.save(deps.storage, cw20.as_str(), &true, env.block.height)?;
+        }
+        PendingReply::Mint { cw20 } => {
+            result.map_err(|reason| ContractError::MintFailed {
+                cw20: cw20.to_string(),
+                reason,
+            })?;
+            if !CW20_CONTRACTS.contains(deps.storage, cw20.as_str()) {
+                return Err(ContractError::DenomNotCreated { cw20: cw20.to_string() });
+            }
+        }
+        PendingReply::Release { cw20, recipient } => {
+            result.map_err(|reason| ContractError::ReleaseFailed {
+                cw20: cw20.to_string(),
+                recipient: recipient.to_string(),
+                reason,
+            })?;
+        }
+        PendingReply::DeferrableRelease { cw20, claimant, amount } => {
+            // state changes of the failed transfer are already reverted, the burn still follows
+            if let Err(reason) = result {
+                record_redemption_claim(deps.storage, &cw20, &claimant, amount)?;
+                return Ok(Response::new().add_event(defer_redemption_event(&cw20, &claimant, amount, &reason)));
+            }
+        }
+        PendingReply::Burn(redemption) => {
+            result.map_err(|reason| ContractError::BurnFailed {
+                cw20: redemption.cw20.to_string(),
+                reason,
+            })?;
+            commit_redemption(deps.storage, &env, &redemption)?;
+        }
+    }
+    Ok(Response::new())
+}
+
+fn commit_redemption(storage: &mut dyn Storage, env: &Env, redemption: &PendingRedemption) -> StdResult<()> {
+    let cw20 = redemption.cw20.as_str();
+    decrease_supply(storage, env.block.height, cw20, redemption.amount)?;
+    let fee = redemption.fee_shares.iter().fold(Uint128::zero(), |acc, (_, share)| acc + *share);
+    record_redeem_stats(storage, cw20, redemption.amount, fee)?;
+    record_operation(
+        storage,
+        env,
+        OperationKind::Redeem,
+        &redemption.cw20,
+        redemption.amount,
+        &redemption.redeemer,
+        Some(&redemption.recipient),
+    )?;
+    // fee stays locked in the adapter as CW-20 until claimed
+    credit_fees(storage, cw20, &redemption.fee_shares)
+}
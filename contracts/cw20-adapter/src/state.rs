@@ -1,3 +1,418 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Empty, HexBinary, Timestamp, Uint128};
 use cw_item_set::Set;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotMap, Strategy};
 
 pub const CW20_CONTRACTS: Set<&str> = Set::new("contracts", "contracts__counter");
+
+/// History of `CW20_CONTRACTS` membership. Contracts registered before it was introduced have no history
+pub const REGISTRATION_STATUS: SnapshotMap<&str, bool> = SnapshotMap::new(
+    "registration_status",
+    "registration_status__checkpoints",
+    "registration_status__changelog",
+    Strategy::EveryBlock,
+);
+
+/// CW-20 contracts that cannot be wrapped or redeemed. Native transfers are blocked too if the send hook is enabled
+pub const FROZEN_TOKENS: Set<&str> = Set::new("frozen_tokens", "frozen_tokens__counter");
+/// Frozen CW-20 contracts whose freeze was set by the admin, so that only the admin can lift it
+pub const ADMIN_FROZEN_TOKENS: Set<&str> = Set::new("admin_frozen_tokens", "admin_frozen_tokens__counter");
+
+/// CW-20 contracts whose TF denom has the adapter set as before-send hook
+pub const SEND_HOOK_TOKENS: Set<&str> = Set::new("send_hook_tokens", "send_hook_tokens__counter");
+
+/// CW-20 contracts vouched for by the admin, so that wallets can tell them apart from look-alikes
+pub const VERIFIED_TOKENS: Set<&str> = Set::new("verified_tokens", "verified_tokens__counter");
+
+/// Addresses refused by the adapter as depositors, redeemers and recipients
+pub const BLOCKLIST: Set<&Addr> = Set::new("blocklist", "blocklist__counter");
+
+/// Only addresses allowed to use the adapter when `Config::allowlist_enabled` is set
+pub const ALLOWLIST: Set<&Addr> = Set::new("allowlist", "allowlist__counter");
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Config {
+    /// Address allowed to change the configuration. Nobody can if not set
+    pub admin: Option<Addr>,
+    /// Receives registration surcharges. Surcharges stay in the adapter if not set
+    pub treasury: Option<Addr>,
+    /// Charged on top of the denom creation fee for explicit registrations
+    pub registration_surcharge: Vec<Coin>,
+    /// Wrap/unwrap fees applied to tokens without their own settings
+    pub default_fees: FeeSettings,
+    /// Part of collected wrap/unwrap fees (in basis points) credited to the token registrant. Remainder goes to the treasury
+    pub registrant_fee_share_bps: u16,
+    /// If set, only allowlisted addresses can wrap and redeem
+    pub allowlist_enabled: bool,
+    /// Metadata of a TF denom is refreshed on wrap once this many blocks passed since it was last set. Zero disables it
+    pub metadata_refresh_blocks: u64,
+    /// Whether (and how) CW-20s get registered on their first deposit
+    #[serde(default)]
+    pub auto_registration: AutoRegistration,
+    /// Limits applied to tokens without their own
+    #[serde(default)]
+    pub default_limits: TokenLimits,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[cw_serde]
+#[derive(Default)]
+pub enum AutoRegistration {
+    /// Denom creation fee is paid from a sponsor's fee reserve or, if there is none, from the adapter balance
+    #[default]
+    Enabled,
+    /// Denom creation fee can only be paid from a sponsor's fee reserve
+    SponsoredOnly,
+    /// CW-20s have to be registered explicitly
+    Disabled,
+}
+
+#[cw_serde]
+#[derive(Copy, Default)]
+pub struct FeeSettings {
+    /// Taken in TF tokens from the minted amount
+    pub wrap_fee_bps: u16,
+    /// Taken in CW-20 tokens from the released amount
+    pub unwrap_fee_bps: u16,
+}
+
+/// Per-token overrides of `Config::default_fees`
+pub const TOKEN_FEES: Map<&str, FeeSettings> = Map::new("token_fees");
+
+/// Claimable fees keyed by (beneficiary, asset), where asset is either TF denom or CW-20 address
+pub const FEE_BALANCES: Map<(&Addr, &str), Uint128> = Map::new("fee_balances");
+
+/// Details of how a CW-20 contract got registered. Not available for contracts registered before it was introduced
+#[cw_serde]
+pub struct Registration {
+    /// Address that sent the registration message or, for auto-registration, the first depositor
+    pub registrant: Addr,
+    /// Set if the auto-registration fee was paid from a sponsor's fee reserve
+    pub sponsor: Option<Addr>,
+    /// Set if the CW-20 was registered by its first deposit rather than by `RegisterCw20Contract`
+    #[serde(default)]
+    pub auto_registered: bool,
+    /// Exact fee paid, including the surcharge
+    pub fee_paid: Vec<Coin>,
+    pub registered_at_height: u64,
+    /// CW-20 name and symbol, updated whenever metadata of the TF denom is refreshed. Empty for registrations made before
+    /// they were recorded and not refreshed since
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub symbol: String,
+    /// Set if another indexed registration already used the same normalised symbol when it was last recorded, a sign of
+    /// possible spoofing
+    #[serde(default)]
+    pub symbol_collision: bool,
+    /// Same as `symbol_collision` for the name
+    #[serde(default)]
+    pub name_collision: bool,
+}
+
+pub struct RegistrationIndexes<'a> {
+    pub registrant: MultiIndex<'a, Addr, Registration, String>,
+    /// Normalised symbol, see `normalise_token_label`
+    pub symbol: MultiIndex<'a, String, Registration, String>,
+    /// Normalised name, see `normalise_token_label`
+    pub name: MultiIndex<'a, String, Registration, String>,
+}
+
+impl<'a> IndexList<Registration> for RegistrationIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Registration>> + '_> {
+        let v: Vec<&dyn Index<Registration>> = vec![&self.registrant, &self.symbol, &self.name];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Registrations keyed by CW-20 contract. Records written before the indexes were introduced are not indexed
+pub fn registrations<'a>() -> IndexedMap<'a, &'a str, Registration, RegistrationIndexes<'a>> {
+    let indexes = RegistrationIndexes {
+        registrant: MultiIndex::new(|_pk, r| r.registrant.clone(), "registrations", "registrations__registrant"),
+        symbol: MultiIndex::new(|_pk, r| normalise_token_label(&r.symbol), "registrations", "registrations__symbol"),
+        name: MultiIndex::new(|_pk, r| normalise_token_label(&r.name), "registrations", "registrations__name"),
+    };
+    IndexedMap::new("registrations", indexes)
+}
+
+/// Index key of a token symbol or name, so that searches ignore case and surrounding whitespace
+pub fn normalise_token_label(label: &str) -> String {
+    label.trim().to_lowercase()
+}
+
+/// Bank metadata of a TF denom, mirroring `cosmos.bank.v1beta1.Metadata`
+#[cw_serde]
+pub struct DenomMetadata {
+    pub description: String,
+    /// Ordered from the base unit (exponent 0) upwards
+    pub denom_units: Vec<DenomUnit>,
+    pub base: String,
+    pub display: String,
+    pub name: String,
+    pub symbol: String,
+    /// Logo URL or `data:` URI of an embedded logo
+    pub uri: String,
+    /// CW-20 decimals, the exponent of the display unit
+    #[serde(default)]
+    pub decimals: u8,
+}
+
+#[cw_serde]
+pub struct DenomUnit {
+    pub denom: String,
+    pub exponent: u32,
+}
+
+/// Metadata values set by the admin. Each provided value replaces the one coming from the CW-20
+#[cw_serde]
+#[derive(Default)]
+pub struct MetadataOverride {
+    pub name: Option<String>,
+    /// Also used as the display denom unit
+    pub symbol: Option<String>,
+    pub description: Option<String>,
+    pub uri: Option<String>,
+}
+
+pub const METADATA_OVERRIDES: Map<&str, MetadataOverride> = Map::new("metadata_overrides");
+
+#[cw_serde]
+pub enum MetadataSource {
+    /// Values come from the CW-20 only
+    Cw20,
+    /// An admin override was applied
+    Override,
+}
+
+#[cw_serde]
+pub struct MetadataChange {
+    pub seq: u64,
+    pub height: u64,
+    pub source: MetadataSource,
+    pub metadata: DenomMetadata,
+}
+
+/// Every change of TF denom metadata, keyed by (CW-20 contract, per-token sequence number)
+pub const METADATA_HISTORY: Map<(&str, u64), MetadataChange> = Map::new("metadata_history");
+
+/// Metadata last set by the adapter for the TF denom of each CW-20. Missing for denoms whose metadata was never set
+/// since it was introduced
+pub const DENOM_METADATA: Map<&str, DenomMetadata> = Map::new("denom_metadata");
+/// Height at which metadata of the TF denom of each CW-20 was last set or found unchanged
+pub const METADATA_REFRESHED_AT: Map<&str, u64> = Map::new("metadata_refreshed_at");
+
+/// Code a CW-20 contract was running when it got registered
+#[cw_serde]
+pub struct CodePin {
+    pub code_id: u64,
+    pub checksum: HexBinary,
+}
+
+pub const CODE_PINS: Map<&str, CodePin> = Map::new("code_pins");
+
+/// Per-token managers, claimed by the CW-20's minter or wasm admin or appointed by the adapter admin. A manager can set
+/// metadata override, freeze and limits of its token only
+pub const TOKEN_MANAGERS: Map<&str, Addr> = Map::new("token_managers");
+
+#[cw_serde]
+#[derive(Default)]
+pub struct TokenLimits {
+    /// Maximum amount of TF tokens that can be outstanding at any time
+    pub supply_cap: Option<Uint128>,
+    pub min_wrap: Option<Uint128>,
+    pub max_wrap: Option<Uint128>,
+    pub min_redeem: Option<Uint128>,
+    pub max_redeem: Option<Uint128>,
+}
+
+pub const TOKEN_LIMITS: Map<&str, TokenLimits> = Map::new("token_limits");
+
+/// Amount of TF tokens minted and not yet burned by the adapter, per CW-20 contract
+pub const TF_SUPPLY: SnapshotMap<&str, Uint128> =
+    SnapshotMap::new("tf_supply", "tf_supply__checkpoints", "tf_supply__changelog", Strategy::EveryBlock);
+
+/// Sum of `TF_SUPPLY` over all CW-20 contracts
+pub const TOTAL_TF_SUPPLY: Item<Uint128> = Item::new("total_tf_supply");
+
+/// CW-20 amount owed to recipients of redemptions whose transfer failed, keyed by (CW-20 address, claimant). The TF
+/// tokens are already burned, so claims are held by the adapter on top of `TF_SUPPLY`
+pub const REDEMPTION_CLAIMS: Map<(&str, &Addr), Uint128> = Map::new("redemption_claims");
+
+/// Sum of `REDEMPTION_CLAIMS` per CW-20 contract
+pub const REDEMPTION_CLAIM_TOTALS: Map<&str, Uint128> = Map::new("redemption_claim_totals");
+
+/// Sum of `REDEMPTION_CLAIMS` over all CW-20 contracts
+pub const TOTAL_REDEMPTION_CLAIMS: Item<Uint128> = Item::new("total_redemption_claims");
+
+/// Cumulative activity counters, kept per CW-20 contract and adapter-wide
+#[cw_serde]
+#[derive(Default)]
+pub struct TokenStats {
+    /// CW-20 amount wrapped
+    pub wrapped_volume: Uint128,
+    /// TF amount redeemed
+    pub unwrapped_volume: Uint128,
+    pub wrap_count: u64,
+    pub redeem_count: u64,
+    pub unique_depositors: u64,
+    /// Wrap fees collected, in TF tokens
+    #[serde(default)]
+    pub wrap_fees: Uint128,
+    /// Unwrap fees collected, in CW-20 tokens
+    #[serde(default)]
+    pub unwrap_fees: Uint128,
+}
+
+pub const TOKEN_STATS: Map<&str, TokenStats> = Map::new("token_stats");
+pub const ADAPTER_STATS: Item<TokenStats> = Item::new("adapter_stats");
+
+/// Addresses that wrapped a CW-20 contract at least once, keyed by (CW-20 contract, depositor)
+pub const TOKEN_DEPOSITORS: Map<(&str, &Addr), Empty> = Map::new("token_depositors");
+/// Addresses that wrapped any CW-20 at least once
+pub const DEPOSITORS: Map<&Addr, Empty> = Map::new("depositors");
+
+/// Bounds net flow of a token within any period of `window_seconds`, relative to the TF supply at the current window start
+#[cw_serde]
+pub struct RateLimit {
+    pub window_seconds: u64,
+    /// Max net amount wrapped (wraps minus redemptions) per period, in basis points of supply
+    pub max_inflow_bps: Option<u16>,
+    /// Max net amount redeemed (redemptions minus wraps) per period, in basis points of supply
+    pub max_outflow_bps: Option<u16>,
+}
+
+pub const RATE_LIMITS: Map<&str, RateLimit> = Map::new("rate_limits");
+
+#[cw_serde]
+pub struct RateLimitWindow {
+    pub started_at: Timestamp,
+    pub supply_at_start: Uint128,
+    pub inflow: Uint128,
+    pub outflow: Uint128,
+    /// Usage of the window right before this one, zero if there was none
+    #[serde(default)]
+    pub previous_inflow: Uint128,
+    #[serde(default)]
+    pub previous_outflow: Uint128,
+}
+
+pub const RATE_LIMIT_WINDOWS: Map<&str, RateLimitWindow> = Map::new("rate_limit_windows");
+
+/// Funds prepaid by a sponsor to cover auto-registration fees, optionally earmarked for a single CW-20
+#[cw_serde]
+pub struct FeeReserve {
+    pub sponsor: Addr,
+    pub for_cw20: Option<Addr>,
+    pub funds: Vec<Coin>,
+}
+
+impl FeeReserve {
+    /// Key under which the reserve is indexed - empty string for reserves usable by any CW-20
+    pub fn earmark(&self) -> String {
+        self.for_cw20.as_ref().map(Addr::to_string).unwrap_or_default()
+    }
+}
+
+pub struct FeeReserveIndexes<'a> {
+    pub earmark: MultiIndex<'a, String, FeeReserve, (Addr, String)>,
+}
+
+impl<'a> IndexList<FeeReserve> for FeeReserveIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<FeeReserve>> + '_> {
+        let v: Vec<&dyn Index<FeeReserve>> = vec![&self.earmark];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Fee reserves keyed by (sponsor, earmark)
+pub fn fee_reserves<'a>() -> IndexedMap<'a, (&'a Addr, &'a str), FeeReserve, FeeReserveIndexes<'a>> {
+    let indexes = FeeReserveIndexes {
+        earmark: MultiIndex::new(|_pk, r| r.earmark(), "fee_reserves", "fee_reserves__earmark"),
+    };
+    IndexedMap::new("fee_reserves", indexes)
+}
+
+/// Sum of all sponsor reserves per denom. These funds are not available for unsponsored registrations
+pub const FEE_RESERVE_TOTALS: Map<&str, Uint128> = Map::new("fee_reserve_totals");
+
+#[cw_serde]
+pub enum OperationKind {
+    Register,
+    Wrap,
+    Redeem,
+}
+
+/// Entry of the operation log
+#[cw_serde]
+pub struct Operation {
+    pub seq: u64,
+    pub height: u64,
+    pub kind: OperationKind,
+    pub cw20: Addr,
+    /// CW-20 amount wrapped or TF amount redeemed, zero for registrations
+    pub amount: Uint128,
+    /// Registrant, depositor or redeemer
+    pub from: Addr,
+    /// Receiver of minted or released tokens, not set for registrations
+    pub to: Option<Addr>,
+}
+
+pub struct OperationIndexes<'a> {
+    pub cw20: MultiIndex<'a, String, Operation, u64>,
+}
+
+impl<'a> IndexList<Operation> for OperationIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Operation>> + '_> {
+        let v: Vec<&dyn Index<Operation>> = vec![&self.cw20];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Operation log keyed by sequence number
+pub fn operations<'a>() -> IndexedMap<'a, u64, Operation, OperationIndexes<'a>> {
+    let indexes = OperationIndexes {
+        cw20: MultiIndex::new(|_pk, op| op.cw20.to_string(), "operations", "operations__cw20"),
+    };
+    IndexedMap::new("operations", indexes)
+}
+
+/// Operations an address took part in (as `from` or `to`), keyed by (address, sequence number)
+pub const ADDRESS_OPERATIONS: Map<(&Addr, u64), Empty> = Map::new("address_operations");
+
+/// Sequence number of the next operation
+pub const NEXT_OPERATION_SEQ: Item<u64> = Item::new("next_operation_seq");
+
+/// Step of a wrap, redemption or registration dispatched as a submessage, awaiting its reply
+#[cw_serde]
+pub enum PendingReply {
+    /// Registration of `cw20` becomes active once its TF denom is created
+    CreateDenom { cw20: Addr },
+    /// Mint of TF tokens of `cw20`, valid only once the registration is active
+    Mint { cw20: Addr },
+    /// Transfer (or send) of redeemed or claimed `cw20` tokens to `recipient`
+    Release { cw20: Addr, recipient: Addr },
+    /// Transfer of `cw20` tokens owed to `claimant` (a redemption or a returned deposit) that leaves a redemption claim
+    /// for `amount` if it fails
+    DeferrableRelease { cw20: Addr, claimant: Addr, amount: Uint128 },
+    /// Burn of redeemed TF tokens, the last step of a redemption. Its accounting is committed once the burn succeeds
+    Burn(PendingRedemption),
+}
+
+#[cw_serde]
+pub struct PendingRedemption {
+    pub cw20: Addr,
+    pub redeemer: Addr,
+    pub recipient: Addr,
+    /// TF tokens burned, including the fee
+    pub amount: Uint128,
+    /// Fee credited to beneficiaries, in CW-20 tokens
+    pub fee_shares: Vec<(Addr, Uint128)>,
+}
+
+/// Submessages awaiting reply, keyed by reply ID
+pub const PENDING_REPLIES: Map<u64, PendingReply> = Map::new("pending_replies");
+
+/// Reply ID of the next submessage
+pub const NEXT_REPLY_ID: Item<u64> = Item::new("next_reply_id");
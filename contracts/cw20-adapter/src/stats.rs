@@ -0,0 +1,49 @@
+use cosmwasm_std::{Addr, Empty, StdResult, Storage, Uint128};
+
+use crate::state::{TokenStats, ADAPTER_STATS, DEPOSITORS, TOKEN_DEPOSITORS, TOKEN_STATS};
+
+pub fn record_wrap_stats(storage: &mut dyn Storage, cw20_addr: &str, depositor: &Addr, amount: Uint128, fee: Uint128) -> StdResult<()> {
+    let new_token_depositor = !TOKEN_DEPOSITORS.has(storage, (cw20_addr, depositor));
+    if new_token_depositor {
+        TOKEN_DEPOSITORS.save(storage, (cw20_addr, depositor), &Empty {})?;
+    }
+    let new_depositor = !DEPOSITORS.has(storage, depositor);
+    if new_depositor {
+        DEPOSITORS.save(storage, depositor, &Empty {})?;
+    }
+
+    let mut token_stats = TOKEN_STATS.may_load(storage, cw20_addr)?.unwrap_or_default();
+    add_wrap(&mut token_stats, amount, fee, new_token_depositor)?;
+    TOKEN_STATS.save(storage, cw20_addr, &token_stats)?;
+
+    let mut adapter_stats = ADAPTER_STATS.may_load(storage)?.unwrap_or_default();
+    add_wrap(&mut adapter_stats, amount, fee, new_depositor)?;
+    ADAPTER_STATS.save(storage, &adapter_stats)
+}
+
+pub fn record_redeem_stats(storage: &mut dyn Storage, cw20_addr: &str, amount: Uint128, fee: Uint128) -> StdResult<()> {
+    let mut token_stats = TOKEN_STATS.may_load(storage, cw20_addr)?.unwrap_or_default();
+    add_redeem(&mut token_stats, amount, fee)?;
+    TOKEN_STATS.save(storage, cw20_addr, &token_stats)?;
+
+    let mut adapter_stats = ADAPTER_STATS.may_load(storage)?.unwrap_or_default();
+    add_redeem(&mut adapter_stats, amount, fee)?;
+    ADAPTER_STATS.save(storage, &adapter_stats)
+}
+
+fn add_wrap(stats: &mut TokenStats, amount: Uint128, fee: Uint128, new_depositor: bool) -> StdResult<()> {
+    stats.wrapped_volume = stats.wrapped_volume.checked_add(amount)?;
+    stats.wrap_fees = stats.wrap_fees.checked_add(fee)?;
+    stats.wrap_count += 1;
+    if new_depositor {
+        stats.unique_depositors += 1;
+    }
+    Ok(())
+}
+
+fn add_redeem(stats: &mut TokenStats, amount: Uint128, fee: Uint128) -> StdResult<()> {
+    stats.unwrapped_volume = stats.unwrapped_volume.checked_add(amount)?;
+    stats.unwrap_fees = stats.unwrap_fees.checked_add(fee)?;
+    stats.redeem_count += 1;
+    Ok(())
+}
@@ -1,16 +1,121 @@
 #![allow(dead_code)]
 
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::testing::{MockApi, MockStorage};
 use cosmwasm_std::{
-    to_binary, Addr, BalanceResponse, Binary, BlockInfo, Coin, ContractInfo, ContractResult, Env, QuerierResult, SystemError, SystemResult,
-    Timestamp, TransactionInfo, Uint128,
+    from_binary, from_slice, to_binary, Addr, BalanceResponse, Binary, BlockInfo, Coin, ContractInfo, ContractResult, Env, HexBinary, OwnedDeps,
+    Querier, QuerierResult, QueryRequest, Reply, ReplyOn, Response, SubMsgResponse, SubMsgResult, SystemError, SystemResult, Timestamp,
+    TransactionInfo, Uint128, WasmQuery,
 };
-use cw20::TokenInfoResponse;
-use injective_cosmwasm::{HandlesBankBalanceQuery, HandlesFeeQuery, HandlesSmartQuery};
+use cw20::{Cw20QueryMsg, DownloadLogoResponse, LogoInfo, MarketingInfoResponse, MinterResponse, TokenInfoResponse};
+use cw20_adapter::common::get_denom;
+use cw20_adapter::contract::reply;
+use cw20_adapter::state::{DenomMetadata, DenomUnit};
+use injective_cosmwasm::{HandlesBankBalanceQuery, HandlesFeeQuery, HandlesSmartQuery, InjectiveMsgWrapper, InjectiveQueryWrapper, WasmMockQuerier};
+use std::marker::PhantomData;
 
 pub const CONTRACT_ADDRESS: &str = "inj1pvrwmjuusn9wh34j7y520g8gumuy9xtlt6xtzw";
 pub const CW_20_ADDRESS: &str = "inj1pjcw9hhx8kf462qtgu37p7l7shyqgpfr82r6em";
 pub const SENDER: &str = "inj1n0qvel0zfmsxu3q8q23xzjvuwfxn0ydlhgyh7h";
 
+pub const CW_20_CODE_ID: u64 = 1;
+pub const CW_20_CHECKSUM: &str = "13a1fc994cc6d1c81b746ee0c0ff6f90043875e0bf1d9be6b7d779fc978dc2a5";
+
+/// Answers `ContractInfo` and `CodeInfo` queries (which `WasmMockQuerier` does not support) as if every contract was
+/// running `code_id` with `checksum` and had `contract_admin` as wasm admin, and delegates everything else to `base`
+pub struct AdapterMockQuerier {
+    pub base: WasmMockQuerier,
+    pub code_id: u64,
+    pub checksum: HexBinary,
+    pub contract_admin: Option<String>,
+}
+
+#[cw_serde]
+struct MockContractInfoResponse {
+    code_id: u64,
+    creator: String,
+    admin: Option<String>,
+    pinned: bool,
+    ibc_port: Option<String>,
+}
+
+#[cw_serde]
+struct MockCodeInfoResponse {
+    code_id: u64,
+    creator: String,
+    checksum: HexBinary,
+}
+
+impl Querier for AdapterMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        match from_slice::<QueryRequest<InjectiveQueryWrapper>>(bin_request) {
+            Ok(QueryRequest::Wasm(WasmQuery::ContractInfo { .. })) => SystemResult::Ok(ContractResult::from(to_binary(&MockContractInfoResponse {
+                code_id: self.code_id,
+                creator: SENDER.to_string(),
+                admin: self.contract_admin.clone(),
+                pinned: false,
+                ibc_port: None,
+            }))),
+            Ok(QueryRequest::Wasm(WasmQuery::CodeInfo { code_id })) => SystemResult::Ok(ContractResult::from(to_binary(&MockCodeInfoResponse {
+                code_id,
+                creator: SENDER.to_string(),
+                checksum: self.checksum.clone(),
+            }))),
+            _ => self.base.raw_query(bin_request),
+        }
+    }
+}
+
+/// Runs `reply` for every submessage of `response` expecting one, as if the chain executed all of them successfully
+pub fn confirm_submessages(
+    deps: &mut OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>,
+    env: &Env,
+    response: &Response<InjectiveMsgWrapper>,
+) {
+    for submsg in response.messages.iter().filter(|m| m.reply_on != ReplyOn::Never) {
+        let result = SubMsgResult::Ok(SubMsgResponse { events: vec![], data: None });
+        reply(deps.as_mut(), env.clone(), Reply { id: submsg.id, result }).unwrap();
+    }
+}
+
+pub fn mock_dependencies() -> OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper> {
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: AdapterMockQuerier {
+            base: WasmMockQuerier::new(),
+            code_id: CW_20_CODE_ID,
+            checksum: HexBinary::from_hex(CW_20_CHECKSUM).unwrap(),
+            contract_admin: None,
+        },
+        custom_query_type: PhantomData,
+    }
+}
+
+/// Metadata the adapter sets for `CW_20_ADDRESS` as reported by the CW-20 query handlers
+pub fn sol_denom_metadata(description: &str, uri: &str) -> DenomMetadata {
+    let denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS));
+    DenomMetadata {
+        description: description.to_string(),
+        denom_units: vec![
+            DenomUnit {
+                denom: denom.clone(),
+                exponent: 0,
+            },
+            DenomUnit {
+                denom: "SOL".to_string(),
+                exponent: 6,
+            },
+        ],
+        base: denom,
+        display: "SOL".to_string(),
+        name: "Solana".to_string(),
+        symbol: "SOL".to_string(),
+        uri: uri.to_string(),
+        decimals: 6,
+    }
+}
+
 pub fn mock_env(addr: &str) -> Env {
     Env {
         block: BlockInfo {
@@ -41,6 +146,97 @@ pub fn create_cw20_info_query_handler() -> Option<Box<dyn HandlesSmartQuery>> {
     Some(Box::new(A()))
 }
 
+/// Answers `TokenInfo` of CW-20s in `renamed` as "Wrapped Ether" (WETH) and of any other CW-20 as "Solana" (SOL)
+pub fn create_renamed_cw20_info_query_handler(renamed: Vec<&'static str>) -> Option<Box<dyn HandlesSmartQuery>> {
+    struct A {
+        renamed: Vec<&'static str>,
+    }
+    impl HandlesSmartQuery for A {
+        fn handle(&self, contract_addr: &str, _: &Binary) -> QuerierResult {
+            let (name, symbol) = if self.renamed.contains(&contract_addr) {
+                ("Wrapped Ether", "WETH")
+            } else {
+                ("Solana", "SOL")
+            };
+            let response = TokenInfoResponse {
+                name: name.to_string(),
+                symbol: symbol.to_string(),
+                decimals: 6,
+                total_supply: Uint128::new(1000),
+            };
+            SystemResult::Ok(ContractResult::from(to_binary(&response)))
+        }
+    }
+    Some(Box::new(A { renamed }))
+}
+
+/// Answers `TokenInfo` like `create_cw20_info_query_handler`, `MarketingInfo` with a description and `logo`, and
+/// `DownloadLogo` with a PNG
+pub fn create_cw20_marketing_query_handler(logo: Option<LogoInfo>) -> Option<Box<dyn HandlesSmartQuery>> {
+    struct A {
+        logo: Option<LogoInfo>,
+    }
+    impl HandlesSmartQuery for A {
+        fn handle(&self, _: &str, msg: &Binary) -> QuerierResult {
+            let response = match from_binary(msg).unwrap() {
+                Cw20QueryMsg::TokenInfo {} => to_binary(&TokenInfoResponse {
+                    name: "Solana".to_string(),
+                    symbol: "SOL".to_string(),
+                    decimals: 6,
+                    total_supply: Uint128::new(1000),
+                }),
+                Cw20QueryMsg::MarketingInfo {} => to_binary(&MarketingInfoResponse {
+                    project: None,
+                    description: Some("Solana on Injective".to_string()),
+                    logo: self.logo.clone(),
+                    marketing: None,
+                }),
+                Cw20QueryMsg::DownloadLogo {} => to_binary(&DownloadLogoResponse {
+                    mime_type: "image/png".to_string(),
+                    data: Binary::from(b"logo".to_vec()),
+                }),
+                _ => {
+                    return SystemResult::Err(SystemError::UnsupportedRequest {
+                        kind: "cw20 query".to_string(),
+                    })
+                }
+            };
+            SystemResult::Ok(ContractResult::from(response))
+        }
+    }
+    Some(Box::new(A { logo }))
+}
+
+/// Answers `TokenInfo` like `create_cw20_info_query_handler` and `Minter` with `minter`
+pub fn create_cw20_minter_query_handler(minter: &str) -> Option<Box<dyn HandlesSmartQuery>> {
+    struct A {
+        minter: String,
+    }
+    impl HandlesSmartQuery for A {
+        fn handle(&self, _: &str, msg: &Binary) -> QuerierResult {
+            let response = match from_binary(msg).unwrap() {
+                Cw20QueryMsg::TokenInfo {} => to_binary(&TokenInfoResponse {
+                    name: "Solana".to_string(),
+                    symbol: "SOL".to_string(),
+                    decimals: 6,
+                    total_supply: Uint128::new(1000),
+                }),
+                Cw20QueryMsg::Minter {} => to_binary(&Some(MinterResponse {
+                    minter: self.minter.clone(),
+                    cap: None,
+                })),
+                _ => {
+                    return SystemResult::Err(SystemError::UnsupportedRequest {
+                        kind: "cw20 query".to_string(),
+                    })
+                }
+            };
+            SystemResult::Ok(ContractResult::from(response))
+        }
+    }
+    Some(Box::new(A { minter: minter.to_string() }))
+}
+
 pub fn create_cw20_failing_info_query_handler() -> Option<Box<dyn HandlesSmartQuery>> {
     struct A();
     impl HandlesSmartQuery for A {
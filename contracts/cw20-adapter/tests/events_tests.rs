@@ -0,0 +1,424 @@
+mod common;
+
+use cosmwasm_std::{
+    testing::{mock_env, mock_info},
+    Addr, Coin, Env, Event, Uint128,
+};
+
+use cw20_adapter::{
+    common::get_denom,
+    execute_admin::handle_update_config,
+    execute_fee_reserve::{handle_deposit_fee_reserve, handle_withdraw_fee_reserve},
+    execute_fees::{handle_claim_fees, handle_set_token_fees},
+    execute_limits::handle_set_token_limits,
+    execute_metadata::{handle_set_metadata_override, handle_update_metadata},
+    execute_rate_limits::handle_set_rate_limit,
+    execute_receive::handle_on_received_cw20_funds_msg,
+    execute_redeem::handle_redeem_msg,
+    execute_register::handle_register_msg,
+    execute_token_manager::handle_set_token_manager,
+    execute_token_restrictions::{handle_set_send_hook, handle_set_token_frozen, handle_set_token_verified},
+    msg::ConfigUpdate,
+    state::{Config, FeeSettings, MetadataOverride, RateLimit, TokenLimits, CONFIG, CW20_CONTRACTS},
+};
+use injective_cosmwasm::WasmMockQuerier;
+
+use crate::common::{
+    confirm_submessages, create_custom_bank_balance_query_handler, create_cw20_info_query_handler, mock_dependencies, CONTRACT_ADDRESS,
+    CW_20_ADDRESS, CW_20_CHECKSUM, CW_20_CODE_ID, SENDER,
+};
+
+const ADMIN: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+const TREASURY: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+
+fn adapter_env() -> Env {
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    env
+}
+
+fn denom() -> String {
+    get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS))
+}
+
+#[test]
+fn it_emits_register_event() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+
+    let response = handle_register_msg(
+        deps.as_mut(),
+        adapter_env(),
+        mock_info(SENDER, &[Coin::new(10, "inj")]),
+        Addr::unchecked(CW_20_ADDRESS),
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        response.events,
+        vec![Event::new("register")
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("denom", denom())
+            .add_attribute("registrant", SENDER)
+            .add_attribute("fee_paid", "10inj")],
+        "incorrect events emitted"
+    );
+}
+
+#[test]
+fn it_emits_register_and_wrap_events_on_auto_registration() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        adapter_env(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(100),
+    )
+    .unwrap();
+
+    assert_eq!(
+        response.events,
+        vec![
+            Event::new("register")
+                .add_attribute("cw20", CW_20_ADDRESS)
+                .add_attribute("denom", denom())
+                .add_attribute("registrant", SENDER)
+                .add_attribute("fee_paid", "10inj"),
+            Event::new("wrap")
+                .add_attribute("cw20", CW_20_ADDRESS)
+                .add_attribute("denom", denom())
+                .add_attribute("sender", SENDER)
+                .add_attribute("recipient", SENDER)
+                .add_attribute("amount", "100")
+                .add_attribute("fee", "0"),
+        ],
+        "incorrect events emitted"
+    );
+}
+
+#[test]
+fn it_emits_wrap_and_redeem_events_with_fees() {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                treasury: Some(Addr::unchecked(TREASURY)),
+                default_fees: FeeSettings {
+                    wrap_fee_bps: 100,
+                    unwrap_fee_bps: 200,
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        adapter_env(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(1000),
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("wrap")
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("denom", denom())
+            .add_attribute("sender", SENDER)
+            .add_attribute("recipient", SENDER)
+            .add_attribute("amount", "1000")
+            .add_attribute("fee", "10")],
+        "incorrect wrap event emitted"
+    );
+
+    let response = handle_redeem_msg(
+        deps.as_mut(),
+        adapter_env(),
+        mock_info(SENDER, &[Coin::new(500, denom())]),
+        Some(TREASURY.to_string()),
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("redeem")
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("denom", denom())
+            .add_attribute("sender", SENDER)
+            .add_attribute("recipient", TREASURY)
+            .add_attribute("amount", "500")
+            .add_attribute("fee", "10")],
+        "incorrect redeem event emitted"
+    );
+    confirm_submessages(&mut deps, &adapter_env(), &response);
+
+    let response = handle_claim_fees(deps.as_mut(), mock_info(TREASURY, &[])).unwrap();
+    assert_eq!(
+        response.events,
+        vec![
+            Event::new("claim_fees")
+                .add_attribute("sender", TREASURY)
+                .add_attribute("asset", denom())
+                .add_attribute("amount", "10"),
+            Event::new("claim_fees")
+                .add_attribute("sender", TREASURY)
+                .add_attribute("asset", CW_20_ADDRESS)
+                .add_attribute("amount", "10"),
+        ],
+        "incorrect claim events emitted"
+    );
+}
+
+#[test]
+fn it_emits_token_frozen_event_on_code_mismatch() {
+    let mut deps = mock_dependencies();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        adapter_env(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(10),
+    )
+    .unwrap();
+
+    deps.querier.code_id = CW_20_CODE_ID + 1;
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        adapter_env(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(10),
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("token_frozen")
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("pinned_code_id", CW_20_CODE_ID.to_string())
+            .add_attribute("pinned_checksum", CW_20_CHECKSUM)
+            .add_attribute("code_id", (CW_20_CODE_ID + 1).to_string())
+            .add_attribute("checksum", CW_20_CHECKSUM)],
+        "incorrect events emitted"
+    );
+}
+
+#[test]
+fn it_emits_update_metadata_event() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+
+    let response = handle_update_metadata(deps.as_mut(), adapter_env(), Addr::unchecked(CW_20_ADDRESS)).unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("update_metadata")
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("denom", denom())],
+        "incorrect events emitted"
+    );
+}
+
+#[test]
+fn it_emits_fee_reserve_events() {
+    let mut deps = mock_dependencies();
+
+    let response = handle_deposit_fee_reserve(
+        deps.as_mut(),
+        mock_info(SENDER, &[Coin::new(10, "inj")]),
+        Some(Addr::unchecked(CW_20_ADDRESS)),
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("deposit_fee_reserve")
+            .add_attribute("sender", SENDER)
+            .add_attribute("funds", "10inj")
+            .add_attribute("cw20", CW_20_ADDRESS)],
+        "incorrect deposit event emitted"
+    );
+
+    let response = handle_withdraw_fee_reserve(deps.as_mut(), mock_info(SENDER, &[]), Some(Addr::unchecked(CW_20_ADDRESS))).unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("withdraw_fee_reserve")
+            .add_attribute("sender", SENDER)
+            .add_attribute("funds", "10inj")
+            .add_attribute("cw20", CW_20_ADDRESS)],
+        "incorrect withdraw event emitted"
+    );
+}
+
+#[test]
+fn it_emits_admin_events() {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+
+    let response = handle_update_config(
+        deps.as_mut(),
+        mock_info(ADMIN, &[]),
+        ConfigUpdate {
+            treasury: Some(TREASURY.to_string()),
+            registration_surcharge: Some(vec![Coin::new(5, "inj")]),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("update_config")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("admin", ADMIN)
+            .add_attribute("treasury", TREASURY)
+            .add_attribute("registration_surcharge", "5inj")
+            .add_attribute("wrap_fee_bps", "0")
+            .add_attribute("unwrap_fee_bps", "0")
+            .add_attribute("registrant_fee_share_bps", "0")
+            .add_attribute("allowlist_enabled", "false")
+            .add_attribute("metadata_refresh_blocks", "0")
+            .add_attribute("auto_registration", "enabled")],
+        "incorrect update_config event emitted"
+    );
+
+    let fees = FeeSettings {
+        wrap_fee_bps: 10,
+        unwrap_fee_bps: 20,
+    };
+    let response = handle_set_token_fees(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), Some(fees)).unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("set_token_fees")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("wrap_fee_bps", "10")
+            .add_attribute("unwrap_fee_bps", "20")],
+        "incorrect set_token_fees event emitted"
+    );
+    let response = handle_set_token_fees(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), None).unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("set_token_fees")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("cw20", CW_20_ADDRESS)],
+        "incorrect event emitted when clearing token fees"
+    );
+
+    let limits = TokenLimits {
+        supply_cap: Some(Uint128::new(1000)),
+        max_redeem: Some(Uint128::new(100)),
+        ..Default::default()
+    };
+    let response = handle_set_token_limits(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), limits).unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("set_token_limits")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("supply_cap", "1000")
+            .add_attribute("max_redeem", "100")],
+        "incorrect set_token_limits event emitted"
+    );
+
+    let rate_limit = RateLimit {
+        window_seconds: 3600,
+        max_inflow_bps: None,
+        max_outflow_bps: Some(500),
+    };
+    let response = handle_set_rate_limit(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), Some(rate_limit)).unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("set_rate_limit")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("window_seconds", "3600")
+            .add_attribute("max_outflow_bps", "500")],
+        "incorrect set_rate_limit event emitted"
+    );
+
+    let response = handle_set_token_frozen(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), true).unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("set_token_frozen")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("frozen", "true")],
+        "incorrect set_token_frozen event emitted"
+    );
+
+    let response = handle_set_token_verified(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), true).unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("set_token_verified")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("verified", "true")],
+        "incorrect set_token_verified event emitted"
+    );
+
+    deps.querier.base.smart_query_handler = create_cw20_info_query_handler();
+    let metadata_override = MetadataOverride {
+        symbol: Some("wSOL".to_string()),
+        ..Default::default()
+    };
+    let response = handle_set_metadata_override(deps.as_mut(), adapter_env(), mock_info(ADMIN, &[]), cw20.clone(), Some(metadata_override)).unwrap();
+    assert_eq!(
+        response.events,
+        vec![
+            Event::new("set_metadata_override")
+                .add_attribute("sender", ADMIN)
+                .add_attribute("cw20", CW_20_ADDRESS)
+                .add_attribute("symbol", "wSOL"),
+            Event::new("update_metadata")
+                .add_attribute("cw20", CW_20_ADDRESS)
+                .add_attribute("denom", denom()),
+        ],
+        "incorrect set_metadata_override events emitted"
+    );
+
+    let response = handle_set_token_manager(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), Some(TREASURY.to_string())).unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("set_token_manager")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("manager", TREASURY)],
+        "incorrect set_token_manager event emitted"
+    );
+
+    let response = handle_set_send_hook(deps.as_mut(), adapter_env(), mock_info(ADMIN, &[]), cw20, true).unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("set_send_hook")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("enabled", "true")],
+        "incorrect set_send_hook event emitted"
+    );
+}
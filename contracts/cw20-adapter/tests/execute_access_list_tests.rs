@@ -0,0 +1,169 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info},
+    Addr, Coin, Event, Uint128,
+};
+
+use cw20_adapter::{
+    common::get_denom,
+    contract::query,
+    error::ContractError,
+    execute_access_list::{handle_update_allowlist, handle_update_blocklist},
+    execute_receive::handle_on_received_cw20_funds_msg,
+    execute_redeem::handle_redeem_msg,
+    msg::QueryMsg,
+    state::{Config, CONFIG, CW20_CONTRACTS},
+};
+
+use crate::common::{mock_dependencies, CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER};
+
+const ADMIN: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+const SANCTIONED: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+
+fn admin_config(allowlist_enabled: bool) -> Config {
+    Config {
+        admin: Some(Addr::unchecked(ADMIN)),
+        allowlist_enabled,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn it_updates_blocklist_with_events() {
+    let mut deps = mock_dependencies();
+    CONFIG.save(&mut deps.storage, &admin_config(false)).unwrap();
+
+    let response = handle_update_blocklist(
+        deps.as_mut(),
+        mock_info(ADMIN, &[]),
+        vec![SANCTIONED.to_string(), SENDER.to_string()],
+        vec![],
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![
+            Event::new("blocklist_add")
+                .add_attribute("sender", ADMIN)
+                .add_attribute("address", SANCTIONED),
+            Event::new("blocklist_add")
+                .add_attribute("sender", ADMIN)
+                .add_attribute("address", SENDER),
+        ],
+        "incorrect events emitted"
+    );
+
+    let response = handle_update_blocklist(
+        deps.as_mut(),
+        mock_info(ADMIN, &[]),
+        vec![],
+        vec![SENDER.to_string(), CW_20_ADDRESS.to_string()],
+    )
+    .unwrap();
+    assert_eq!(
+        response.events,
+        vec![Event::new("blocklist_remove")
+            .add_attribute("sender", ADMIN)
+            .add_attribute("address", SENDER)],
+        "events should be emitted only for changed addresses"
+    );
+
+    let blocked: Vec<Addr> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Blocklist {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(blocked, vec![Addr::unchecked(SANCTIONED)], "incorrect blocklist returned");
+
+    let response = handle_update_blocklist(deps.as_mut(), mock_info(SENDER, &[]), vec![SENDER.to_string()], vec![]).unwrap_err();
+    assert_eq!(response, ContractError::Unauthorized, "incorrect error returned");
+}
+
+#[test]
+fn it_refuses_blocked_depositor_redeemer_and_recipient() {
+    let mut deps = mock_dependencies();
+    CONFIG.save(&mut deps.storage, &admin_config(false)).unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    handle_update_blocklist(deps.as_mut(), mock_info(ADMIN, &[]), vec![SANCTIONED.to_string()], vec![]).unwrap();
+
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let expected_error = ContractError::AddressBlocked {
+        address: SANCTIONED.to_string(),
+    };
+
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SANCTIONED.to_string(),
+        Uint128::new(10),
+    )
+    .unwrap_err();
+    assert_eq!(response, expected_error, "blocked depositor was accepted");
+
+    let denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS));
+    let response = handle_redeem_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SANCTIONED, &[Coin::new(10, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(response, expected_error, "blocked redeemer was accepted");
+
+    let response = handle_redeem_msg(
+        deps.as_mut(),
+        env,
+        mock_info(SENDER, &[Coin::new(10, denom.as_str())]),
+        Some(SANCTIONED.to_string()),
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(response, expected_error, "blocked recipient was accepted");
+}
+
+#[test]
+fn it_accepts_only_allowlisted_addresses_in_allowlist_mode() {
+    let mut deps = mock_dependencies();
+    CONFIG.save(&mut deps.storage, &admin_config(true)).unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    handle_update_allowlist(deps.as_mut(), mock_info(ADMIN, &[]), vec![SENDER.to_string()], vec![]).unwrap();
+
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+
+    handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(10),
+    )
+    .unwrap();
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env,
+        mock_info(CW_20_ADDRESS, &[]),
+        SANCTIONED.to_string(),
+        Uint128::new(10),
+    )
+    .unwrap_err();
+    assert_eq!(
+        response,
+        ContractError::AddressNotAllowlisted {
+            address: SANCTIONED.to_string()
+        },
+        "incorrect error returned"
+    );
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info},
+    Addr, Coin, Uint128,
+};
+
+use cw20_adapter::{
+    contract::{instantiate, query},
+    error::ContractError,
+    execute_admin::handle_update_config,
+    msg::{ConfigUpdate, InstantiateMsg, QueryMsg},
+    state::{AutoRegistration, Config, FeeSettings, TokenLimits, CW20_CONTRACTS},
+};
+use injective_cosmwasm::WasmMockQuerier;
+
+use crate::common::{confirm_submessages, create_cw20_info_query_handler, mock_dependencies, CW_20_ADDRESS, SENDER};
+
+const ADMIN: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+const TREASURY: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+
+#[test]
+fn it_sets_instantiator_as_admin() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), InstantiateMsg::default()).unwrap();
+
+    let config: Config = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.admin, Some(Addr::unchecked(ADMIN)), "incorrect admin set");
+}
+
+#[test]
+fn it_updates_config() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), InstantiateMsg::default()).unwrap();
+
+    handle_update_config(
+        deps.as_mut(),
+        mock_info(ADMIN, &[]),
+        ConfigUpdate {
+            treasury: Some(TREASURY.to_string()),
+            registration_surcharge: Some(vec![Coin::new(1, "inj")]),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    let config: Config = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        Config {
+            admin: Some(Addr::unchecked(ADMIN)),
+            treasury: Some(Addr::unchecked(TREASURY)),
+            registration_surcharge: vec![Coin::new(1, "inj")],
+            ..Default::default()
+        },
+        "incorrect config stored"
+    );
+}
+
+#[test]
+fn it_returns_error_if_config_updated_by_non_admin() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), InstantiateMsg::default()).unwrap();
+
+    let update = ConfigUpdate {
+        admin: Some(SENDER.to_string()),
+        ..Default::default()
+    };
+    let response = handle_update_config(deps.as_mut(), mock_info(SENDER, &[]), update).unwrap_err();
+    assert_eq!(response, ContractError::Unauthorized, "incorrect error returned");
+}
+
+#[test]
+fn it_returns_error_if_default_fees_are_too_high() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), InstantiateMsg::default()).unwrap();
+
+    let update = ConfigUpdate {
+        default_fees: Some(FeeSettings {
+            wrap_fee_bps: 1_001,
+            unwrap_fee_bps: 0,
+        }),
+        ..Default::default()
+    };
+    let response = handle_update_config(deps.as_mut(), mock_info(ADMIN, &[]), update).unwrap_err();
+    assert_eq!(response, ContractError::InvalidFeeBps, "incorrect error returned");
+}
+
+#[test]
+fn it_bootstraps_config_and_registers_cw20s_at_instantiation() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let default_fees = FeeSettings {
+        wrap_fee_bps: 10,
+        unwrap_fee_bps: 20,
+    };
+    let default_limits = TokenLimits {
+        max_wrap: Some(Uint128::new(1_000)),
+        ..Default::default()
+    };
+    let msg = InstantiateMsg {
+        config: ConfigUpdate {
+            treasury: Some(TREASURY.to_string()),
+            default_fees: Some(default_fees),
+            auto_registration: Some(AutoRegistration::SponsoredOnly),
+            default_limits: Some(default_limits.clone()),
+            ..Default::default()
+        },
+        cw20s: vec![Addr::unchecked(CW_20_ADDRESS)],
+    };
+
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info(SENDER, &[]), msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotEnoughBalanceToPayDenomCreationFee,
+        "fees of initial CW-20s were not required"
+    );
+
+    let response = instantiate(deps.as_mut(), mock_env(), mock_info(SENDER, &[Coin::new(10, "inj")]), msg).unwrap();
+    assert_eq!(response.messages.len(), 2, "initial CW-20 was not registered");
+    confirm_submessages(&mut deps, &mock_env(), &response);
+    assert!(CW20_CONTRACTS.contains(&deps.storage, CW_20_ADDRESS), "initial CW-20 was not registered");
+
+    let config: Config = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        Config {
+            admin: Some(Addr::unchecked(SENDER)),
+            treasury: Some(Addr::unchecked(TREASURY)),
+            default_fees,
+            auto_registration: AutoRegistration::SponsoredOnly,
+            default_limits,
+            ..Default::default()
+        },
+        "incorrect config stored"
+    );
+}
+
+#[test]
+fn it_returns_error_if_bootstrap_config_is_invalid() {
+    let mut deps = mock_dependencies();
+    let msg = InstantiateMsg {
+        config: ConfigUpdate {
+            default_limits: Some(TokenLimits {
+                min_wrap: Some(Uint128::new(2)),
+                max_wrap: Some(Uint128::new(1)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidTokenLimits, "incorrect error returned");
+}
@@ -0,0 +1,238 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info},
+    Addr, BankMsg, Coin, CosmosMsg, SubMsg, Uint128,
+};
+
+use cw20_adapter::{
+    contract::query,
+    error::ContractError,
+    execute_fee_reserve::{handle_deposit_fee_reserve, handle_withdraw_fee_reserve},
+    execute_receive::handle_on_received_cw20_funds_msg,
+    msg::QueryMsg,
+    state::{fee_reserves, FeeReserve, CW20_CONTRACTS, FEE_RESERVE_TOTALS},
+};
+use injective_cosmwasm::WasmMockQuerier;
+
+use crate::common::{
+    confirm_submessages, create_custom_bank_balance_query_handler, create_cw20_info_query_handler, mock_dependencies, CONTRACT_ADDRESS,
+    CW_20_ADDRESS, SENDER,
+};
+
+const SPONSOR: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+const OTHER_SPONSOR: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+
+#[test]
+fn it_tracks_deposits_per_sponsor_and_earmark() {
+    let mut deps = mock_dependencies();
+
+    handle_deposit_fee_reserve(deps.as_mut(), mock_info(SPONSOR, &[Coin::new(10, "inj")]), None).unwrap();
+    handle_deposit_fee_reserve(deps.as_mut(), mock_info(SPONSOR, &[Coin::new(15, "inj")]), None).unwrap();
+    handle_deposit_fee_reserve(
+        deps.as_mut(),
+        mock_info(SPONSOR, &[Coin::new(10, "inj")]),
+        Some(Addr::unchecked(CW_20_ADDRESS)),
+    )
+    .unwrap();
+
+    let reserves: Vec<FeeReserve> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FeeReserves {
+                sponsor: SPONSOR.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        reserves,
+        vec![
+            FeeReserve {
+                sponsor: Addr::unchecked(SPONSOR),
+                for_cw20: None,
+                funds: vec![Coin::new(25, "inj")],
+            },
+            FeeReserve {
+                sponsor: Addr::unchecked(SPONSOR),
+                for_cw20: Some(Addr::unchecked(CW_20_ADDRESS)),
+                funds: vec![Coin::new(10, "inj")],
+            },
+        ],
+        "incorrect reserves returned"
+    );
+    assert_eq!(
+        FEE_RESERVE_TOTALS.load(&deps.storage, "inj").unwrap(),
+        Uint128::new(35),
+        "incorrect reserved total"
+    );
+}
+
+#[test]
+fn it_returns_error_if_depositing_no_funds() {
+    let mut deps = mock_dependencies();
+    let response = handle_deposit_fee_reserve(deps.as_mut(), mock_info(SPONSOR, &[]), None).unwrap_err();
+    assert_eq!(response, ContractError::NoFundsProvided, "incorrect error returned");
+}
+
+#[test]
+fn it_returns_error_if_deposit_does_not_cover_creation_fee() {
+    let mut deps = mock_dependencies();
+    let response = handle_deposit_fee_reserve(deps.as_mut(), mock_info(SPONSOR, &[Coin::new(1, "inj")]), None).unwrap_err();
+    assert_eq!(response, ContractError::FeeReserveDepositTooLow, "incorrect error returned");
+    assert!(
+        FEE_RESERVE_TOTALS.may_load(&deps.storage, "inj").unwrap().is_none(),
+        "deposit was reserved"
+    );
+}
+
+#[test]
+fn it_withdraws_whole_reserve() {
+    let mut deps = mock_dependencies();
+    handle_deposit_fee_reserve(deps.as_mut(), mock_info(SPONSOR, &[Coin::new(10, "inj")]), None).unwrap();
+
+    let response = handle_withdraw_fee_reserve(deps.as_mut(), mock_info(SPONSOR, &[]), None).unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: SPONSOR.to_string(),
+            amount: vec![Coin::new(10, "inj")],
+        }))],
+        "incorrect withdraw message"
+    );
+    assert_eq!(
+        FEE_RESERVE_TOTALS.load(&deps.storage, "inj").unwrap(),
+        Uint128::zero(),
+        "reserved total was not released"
+    );
+
+    let response = handle_withdraw_fee_reserve(deps.as_mut(), mock_info(SPONSOR, &[]), None).unwrap_err();
+    assert_eq!(response, ContractError::FeeReserveNotFound, "incorrect error returned");
+}
+
+#[test]
+fn it_pays_auto_registration_from_earmarked_reserve_first() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(20, "inj")),
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    handle_deposit_fee_reserve(deps.as_mut(), mock_info(SPONSOR, &[Coin::new(10, "inj")]), None).unwrap();
+    handle_deposit_fee_reserve(
+        deps.as_mut(),
+        mock_info(OTHER_SPONSOR, &[Coin::new(10, "inj")]),
+        Some(Addr::unchecked(CW_20_ADDRESS)),
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(100),
+    )
+    .unwrap();
+    confirm_submessages(&mut deps, &env, &response);
+
+    assert!(CW20_CONTRACTS.contains(&deps.storage, CW_20_ADDRESS), "contract wasn't registered");
+    let other_reserves: Vec<FeeReserve> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FeeReserves {
+                sponsor: OTHER_SPONSOR.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(other_reserves.is_empty(), "earmarked reserve was not used");
+    assert_eq!(
+        FEE_RESERVE_TOTALS.load(&deps.storage, "inj").unwrap(),
+        Uint128::new(10),
+        "general reserve should stay untouched"
+    );
+}
+
+#[test]
+fn it_does_not_spend_reserved_funds_of_other_tokens() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    handle_deposit_fee_reserve(
+        deps.as_mut(),
+        mock_info(SPONSOR, &[Coin::new(10, "inj")]),
+        Some(Addr::unchecked("inj1h0y3hssxf4vsdacfmjg720642cvpxwyqh35kpn")),
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let response =
+        handle_on_received_cw20_funds_msg(deps.as_mut(), env, mock_info(CW_20_ADDRESS, &[]), SENDER.to_string(), Uint128::new(100)).unwrap_err();
+
+    assert_eq!(response, ContractError::NotEnoughBalanceToPayDenomCreationFee, "incorrect error returned");
+}
+
+#[test]
+fn it_skips_reserves_that_cannot_cover_fee() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(15, "inj")),
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    // reserves left below a fee that rose after they were deposited, all sorting before the sponsor that can pay
+    for i in 0..10 {
+        let sponsor = Addr::unchecked(format!("inj1{i:0>38}"));
+        let reserve = FeeReserve {
+            sponsor: sponsor.clone(),
+            for_cw20: None,
+            funds: vec![Coin::new(1, "inj")],
+        };
+        fee_reserves().save(&mut deps.storage, (&sponsor, ""), &reserve).unwrap();
+    }
+    FEE_RESERVE_TOTALS.save(&mut deps.storage, "inj", &Uint128::new(10)).unwrap();
+    handle_deposit_fee_reserve(deps.as_mut(), mock_info(SPONSOR, &[Coin::new(15, "inj")]), None).unwrap();
+
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(100),
+    )
+    .unwrap();
+    confirm_submessages(&mut deps, &env, &response);
+
+    assert!(CW20_CONTRACTS.contains(&deps.storage, CW_20_ADDRESS), "contract wasn't registered");
+    assert!(
+        response.messages.contains(&SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: SPONSOR.to_string(),
+            amount: vec![Coin::new(5, "inj")],
+        }))),
+        "remaining funds of the paying reserve were not refunded"
+    );
+    assert!(
+        fee_reserves().may_load(&deps.storage, (&Addr::unchecked(SPONSOR), "")).unwrap().is_none(),
+        "reserve unable to cover another fee was kept"
+    );
+    assert_eq!(
+        FEE_RESERVE_TOTALS.load(&deps.storage, "inj").unwrap(),
+        Uint128::new(10),
+        "only the skipped reserves should stay reserved"
+    );
+}
@@ -0,0 +1,315 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info, MockApi, MockStorage},
+    to_binary, Addr, BankMsg, Coin, CosmosMsg, OwnedDeps, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use cw20_adapter::{
+    common::get_denom,
+    contract::query,
+    error::ContractError,
+    execute_fees::{handle_claim_fees, handle_set_token_fees},
+    execute_receive::handle_on_received_cw20_funds_msg,
+    execute_redeem::handle_redeem_msg,
+    msg::{FeeBalance, QueryMsg, SimulationResponse},
+    state::{registrations, Config, FeeSettings, Registration, CONFIG, CW20_CONTRACTS},
+};
+use injective_cosmwasm::{InjectiveMsg, InjectiveMsgWrapper, InjectiveQueryWrapper, InjectiveRoute};
+
+use crate::common::{confirm_submessages, mock_dependencies, AdapterMockQuerier, CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER};
+
+const ADMIN: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+const TREASURY: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+const REGISTRANT: &str = "inj1h0y3hssxf4vsdacfmjg720642cvpxwyqh35kpn";
+
+fn setup_with_fees(fees: FeeSettings) -> OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper> {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                treasury: Some(Addr::unchecked(TREASURY)),
+                default_fees: fees,
+                registrant_fee_share_bps: 2_500,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    registrations()
+        .save(
+            &mut deps.storage,
+            CW_20_ADDRESS,
+            &Registration {
+                registrant: Addr::unchecked(REGISTRANT),
+                sponsor: None,
+                auto_registered: false,
+                fee_paid: vec![Coin::new(10, "inj")],
+                registered_at_height: 1,
+                name: "Solana".to_string(),
+                symbol: "SOL".to_string(),
+                symbol_collision: false,
+                name_collision: false,
+            },
+        )
+        .unwrap();
+    deps
+}
+
+fn fee_balances(deps: &OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>, beneficiary: &str) -> Vec<FeeBalance> {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FeeBalances {
+                beneficiary: beneficiary.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn it_takes_wrap_fee_in_tf_tokens() {
+    let mut deps = setup_with_fees(FeeSettings {
+        wrap_fee_bps: 100,
+        unwrap_fee_bps: 0,
+    });
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+
+    let response =
+        handle_on_received_cw20_funds_msg(deps.as_mut(), env, mock_info(CW_20_ADDRESS, &[]), SENDER.to_string(), Uint128::new(1000)).unwrap();
+    assert_eq!(response.messages.len(), 2, "incorrect number of messages returned");
+
+    let denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS));
+    let expected_mints = [(SENDER, 990u128), (CONTRACT_ADDRESS, 10u128)];
+    for (message, (expected_recipient, expected_amount)) in response.messages.iter().zip(expected_mints) {
+        if let SubMsg {
+            msg: CosmosMsg::Custom(InjectiveMsgWrapper { route, msg_data }),
+            ..
+        } = message
+        {
+            assert_eq!(route, &InjectiveRoute::Tokenfactory, "submessage had wrong route");
+            if let InjectiveMsg::Mint { amount, mint_to, .. } = msg_data {
+                assert_eq!(
+                    &Coin::new(expected_amount, denom.as_str()),
+                    amount,
+                    "incorrect amount in the mint message"
+                );
+                assert_eq!(expected_recipient, mint_to.as_str(), "incorrect recipient in the mint message");
+            } else {
+                panic!("incorrect injective message found")
+            }
+        } else {
+            panic!("incorrect submessage type found")
+        }
+    }
+
+    assert_eq!(
+        fee_balances(&deps, REGISTRANT),
+        vec![FeeBalance {
+            asset: denom.clone(),
+            amount: Uint128::new(2)
+        }],
+        "incorrect registrant share"
+    );
+    assert_eq!(
+        fee_balances(&deps, TREASURY),
+        vec![FeeBalance {
+            asset: denom,
+            amount: Uint128::new(8)
+        }],
+        "incorrect treasury share"
+    );
+}
+
+#[test]
+fn it_does_not_share_fees_with_first_depositor_of_auto_registered_token() {
+    const SPONSOR: &str = "inj1n0qvel0zfmsxu3q8q23xzjvuwfxn0ydlhgyh7h";
+    for (sponsor, expected_shares) in [
+        (None, vec![(TREASURY, 10u128)]),
+        (Some(SPONSOR), vec![(SPONSOR, 2u128), (TREASURY, 8u128)]),
+    ] {
+        let mut deps = setup_with_fees(FeeSettings {
+            wrap_fee_bps: 100,
+            unwrap_fee_bps: 0,
+        });
+        registrations()
+            .update(&mut deps.storage, CW_20_ADDRESS, |registration| -> StdResult<_> {
+                Ok(Registration {
+                    sponsor: sponsor.map(Addr::unchecked),
+                    auto_registered: true,
+                    ..registration.unwrap()
+                })
+            })
+            .unwrap();
+        let mut env = mock_env();
+        env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+
+        handle_on_received_cw20_funds_msg(deps.as_mut(), env, mock_info(CW_20_ADDRESS, &[]), SENDER.to_string(), Uint128::new(1000)).unwrap();
+
+        let denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS));
+        assert!(fee_balances(&deps, REGISTRANT).is_empty(), "first depositor got a fee share");
+        for (beneficiary, amount) in expected_shares {
+            assert_eq!(
+                fee_balances(&deps, beneficiary),
+                vec![FeeBalance {
+                    asset: denom.clone(),
+                    amount: Uint128::new(amount)
+                }],
+                "incorrect share of {beneficiary}"
+            );
+        }
+    }
+}
+
+#[test]
+fn it_takes_unwrap_fee_in_cw20_tokens() {
+    let mut deps = setup_with_fees(FeeSettings {
+        wrap_fee_bps: 0,
+        unwrap_fee_bps: 50,
+    });
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+
+    let coins_to_burn = Coin::new(1000, get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS)));
+    let response = handle_redeem_msg(deps.as_mut(), env.clone(), mock_info(SENDER, &[coins_to_burn]), None, None).unwrap();
+    confirm_submessages(&mut deps, &env, &response);
+
+    assert_eq!(
+        response.messages[0].msg,
+        CosmosMsg::from(WasmMsg::Execute {
+            contract_addr: CW_20_ADDRESS.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: SENDER.to_string(),
+                amount: Uint128::new(995),
+            })
+            .unwrap(),
+            funds: vec![],
+        }),
+        "incorrect transfer message"
+    );
+    assert_eq!(
+        fee_balances(&deps, TREASURY),
+        vec![FeeBalance {
+            asset: CW_20_ADDRESS.to_string(),
+            amount: Uint128::new(4)
+        }],
+        "incorrect treasury share"
+    );
+
+    let simulation: SimulationResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SimulateRedeem {
+                addr: CW_20_ADDRESS.to_string(),
+                amount: Uint128::new(1000),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            amount_out: Uint128::new(995),
+            fee: Uint128::new(5)
+        },
+        "incorrect simulation"
+    );
+}
+
+#[test]
+fn it_claims_accrued_fees() {
+    let mut deps = setup_with_fees(FeeSettings {
+        wrap_fee_bps: 100,
+        unwrap_fee_bps: 100,
+    });
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS));
+
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(1000),
+    )
+    .unwrap();
+    confirm_submessages(&mut deps, &env, &response);
+    let response = handle_redeem_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SENDER, &[Coin::new(500, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap();
+    confirm_submessages(&mut deps, &env, &response);
+
+    let response = handle_claim_fees(deps.as_mut(), mock_info(TREASURY, &[])).unwrap();
+    assert_eq!(
+        response.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: TREASURY.to_string(),
+                amount: vec![Coin::new(8, denom.as_str())],
+            })),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: CW_20_ADDRESS.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: TREASURY.to_string(),
+                    amount: Uint128::new(4),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ],
+        "incorrect claim messages"
+    );
+
+    let response = handle_claim_fees(deps.as_mut(), mock_info(TREASURY, &[])).unwrap_err();
+    assert_eq!(response, ContractError::NoFeesToClaim, "fees were claimed twice");
+}
+
+#[test]
+fn it_sets_token_fee_override() {
+    let mut deps = setup_with_fees(FeeSettings::default());
+    let fees = FeeSettings {
+        wrap_fee_bps: 30,
+        unwrap_fee_bps: 0,
+    };
+
+    let response = handle_set_token_fees(deps.as_mut(), mock_info(SENDER, &[]), Addr::unchecked(CW_20_ADDRESS), Some(fees)).unwrap_err();
+    assert_eq!(response, ContractError::Unauthorized, "incorrect error returned");
+
+    handle_set_token_fees(deps.as_mut(), mock_info(ADMIN, &[]), Addr::unchecked(CW_20_ADDRESS), Some(fees)).unwrap();
+    let simulation: SimulationResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SimulateWrap {
+                addr: CW_20_ADDRESS.to_string(),
+                amount: Uint128::new(10_000),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            amount_out: Uint128::new(9_970),
+            fee: Uint128::new(30)
+        },
+        "incorrect simulation"
+    );
+}
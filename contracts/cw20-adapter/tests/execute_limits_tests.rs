@@ -0,0 +1,259 @@
+mod common;
+
+use cosmwasm_std::{
+    testing::{mock_env, mock_info},
+    Addr, Coin, Uint128,
+};
+
+use cw20_adapter::{
+    common::get_denom,
+    error::ContractError,
+    execute_limits::handle_set_token_limits,
+    execute_receive::handle_on_received_cw20_funds_msg,
+    execute_redeem::handle_redeem_msg,
+    execute_register::handle_register_msg,
+    state::{Config, TokenLimits, CONFIG, CW20_CONTRACTS, TF_SUPPLY, TOKEN_LIMITS},
+};
+use injective_cosmwasm::WasmMockQuerier;
+
+use crate::common::{confirm_submessages, create_cw20_info_query_handler, mock_dependencies, CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER};
+
+const ADMIN: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+
+#[test]
+fn it_sets_limits_at_registration_by_admin_only() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let limits = TokenLimits {
+        supply_cap: Some(Uint128::new(1_000)),
+        max_wrap: Some(Uint128::new(100)),
+        ..Default::default()
+    };
+
+    let err = handle_register_msg(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(SENDER, &[Coin::new(10, "inj")]),
+        Addr::unchecked(CW_20_ADDRESS),
+        Some(limits.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "registrant set limits");
+
+    handle_register_msg(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(ADMIN, &[Coin::new(10, "inj")]),
+        Addr::unchecked(CW_20_ADDRESS),
+        Some(limits.clone()),
+    )
+    .unwrap();
+
+    assert_eq!(TOKEN_LIMITS.load(&deps.storage, CW_20_ADDRESS).unwrap(), limits, "limits were not stored");
+}
+
+#[test]
+fn it_returns_error_if_min_above_max() {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+
+    let limits = TokenLimits {
+        min_redeem: Some(Uint128::new(10)),
+        max_redeem: Some(Uint128::new(5)),
+        ..Default::default()
+    };
+    let response = handle_set_token_limits(deps.as_mut(), mock_info(ADMIN, &[]), Addr::unchecked(CW_20_ADDRESS), limits.clone()).unwrap_err();
+    assert_eq!(response, ContractError::InvalidTokenLimits, "incorrect error returned");
+
+    let response = handle_set_token_limits(deps.as_mut(), mock_info(SENDER, &[]), Addr::unchecked(CW_20_ADDRESS), limits).unwrap_err();
+    assert_eq!(response, ContractError::Unauthorized, "incorrect error returned");
+}
+
+#[test]
+fn it_enforces_wrap_limits_and_supply_cap() {
+    let mut deps = mock_dependencies();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    TOKEN_LIMITS
+        .save(
+            &mut deps.storage,
+            CW_20_ADDRESS,
+            &TokenLimits {
+                supply_cap: Some(Uint128::new(150)),
+                min_wrap: Some(Uint128::new(10)),
+                max_wrap: Some(Uint128::new(100)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(5),
+    )
+    .unwrap_err();
+    assert_eq!(
+        response,
+        ContractError::AmountBelowMinimum { min: Uint128::new(10) },
+        "incorrect error returned"
+    );
+
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(101),
+    )
+    .unwrap_err();
+    assert_eq!(
+        response,
+        ContractError::AmountAboveMaximum { max: Uint128::new(100) },
+        "incorrect error returned"
+    );
+
+    handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(100),
+    )
+    .unwrap();
+    assert_eq!(
+        TF_SUPPLY.load(&deps.storage, CW_20_ADDRESS).unwrap(),
+        Uint128::new(100),
+        "incorrect supply"
+    );
+
+    let response =
+        handle_on_received_cw20_funds_msg(deps.as_mut(), env, mock_info(CW_20_ADDRESS, &[]), SENDER.to_string(), Uint128::new(51)).unwrap_err();
+    assert_eq!(
+        response,
+        ContractError::SupplyCapExceeded { cap: Uint128::new(150) },
+        "incorrect error returned"
+    );
+}
+
+#[test]
+fn it_enforces_redeem_limits_and_tracks_supply() {
+    let mut deps = mock_dependencies();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    TF_SUPPLY.save(&mut deps.storage, CW_20_ADDRESS, &Uint128::new(100), 0).unwrap();
+    TOKEN_LIMITS
+        .save(
+            &mut deps.storage,
+            CW_20_ADDRESS,
+            &TokenLimits {
+                max_redeem: Some(Uint128::new(50)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS));
+
+    let response = handle_redeem_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SENDER, &[Coin::new(60, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        response,
+        ContractError::AmountAboveMaximum { max: Uint128::new(50) },
+        "incorrect error returned"
+    );
+
+    let response = handle_redeem_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SENDER, &[Coin::new(40, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        TF_SUPPLY.load(&deps.storage, CW_20_ADDRESS).unwrap(),
+        Uint128::new(100),
+        "supply was decreased before the burn was confirmed"
+    );
+    confirm_submessages(&mut deps, &env, &response);
+    assert_eq!(
+        TF_SUPPLY.load(&deps.storage, CW_20_ADDRESS).unwrap(),
+        Uint128::new(60),
+        "incorrect supply"
+    );
+}
+
+#[test]
+fn it_applies_default_limits_to_tokens_without_own() {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                default_limits: TokenLimits {
+                    max_wrap: Some(Uint128::new(50)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(51),
+    )
+    .unwrap_err();
+    assert_eq!(
+        response,
+        ContractError::AmountAboveMaximum { max: Uint128::new(50) },
+        "default limits were not applied"
+    );
+
+    handle_set_token_limits(
+        deps.as_mut(),
+        mock_info(ADMIN, &[]),
+        Addr::unchecked(CW_20_ADDRESS),
+        TokenLimits::default(),
+    )
+    .unwrap();
+    handle_on_received_cw20_funds_msg(deps.as_mut(), env, mock_info(CW_20_ADDRESS, &[]), SENDER.to_string(), Uint128::new(51)).unwrap();
+}
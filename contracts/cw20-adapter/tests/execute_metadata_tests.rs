@@ -1,63 +1,416 @@
 mod common;
 
-use cosmwasm_std::testing::mock_env;
-use cosmwasm_std::{Addr, CosmosMsg, SubMsg};
-use cw20_adapter::common::get_denom;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{from_binary, Addr, Coin, OwnedDeps, SubMsg, Uint128};
+use cw20::LogoInfo;
+use cw20_adapter::common::create_set_denom_metadata_msg;
+use cw20_adapter::contract::query;
 use cw20_adapter::error::ContractError;
-use cw20_adapter::execute_metadata::handle_update_metadata;
-use cw20_adapter::state::CW20_CONTRACTS;
-use injective_cosmwasm::{mock_dependencies, InjectiveMsg, InjectiveMsgWrapper, InjectiveRoute, WasmMockQuerier};
+use cw20_adapter::execute_metadata::{handle_refresh_all_metadata, handle_set_metadata_override, handle_update_metadata};
+use cw20_adapter::execute_receive::handle_on_received_cw20_funds_msg;
+use cw20_adapter::msg::{QueryMsg, RefreshMetadataResponse};
+use cw20_adapter::state::{Config, MetadataChange, MetadataOverride, MetadataSource, CONFIG, CW20_CONTRACTS};
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper, WasmMockQuerier};
 
-use crate::common::{create_cw20_info_query_handler, CONTRACT_ADDRESS, CW_20_ADDRESS};
+use crate::common::{
+    confirm_submessages, create_custom_bank_balance_query_handler, create_cw20_info_query_handler, create_cw20_marketing_query_handler,
+    mock_dependencies, sol_denom_metadata, AdapterMockQuerier, CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER,
+};
+
+const OTHER_CW20: &str = "inj1h0y3hssxf4vsdacfmjg720642cvpxwyqh35kpn";
+const ADMIN: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+
+fn update_metadata(querier: WasmMockQuerier) -> Vec<SubMsg<InjectiveMsgWrapper>> {
+    let mut deps = mock_dependencies();
+    deps.querier.base = querier;
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+
+    handle_update_metadata(deps.as_mut(), env, Addr::unchecked(CW_20_ADDRESS))
+        .unwrap()
+        .messages
+}
 
 #[test]
 fn it_updates_metadata() {
+    let messages = update_metadata(WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    });
+    assert_eq!(
+        messages,
+        vec![SubMsg::new(create_set_denom_metadata_msg(
+            &Addr::unchecked(CONTRACT_ADDRESS),
+            &sol_denom_metadata("", "")
+        ))],
+        "incorrect set metadata message for CW-20 without marketing"
+    );
+}
+
+#[test]
+fn it_updates_metadata_with_marketing_info() {
+    let messages = update_metadata(WasmMockQuerier {
+        smart_query_handler: create_cw20_marketing_query_handler(Some(LogoInfo::Url("https://solana.com/logo.png".to_string()))),
+        ..Default::default()
+    });
+    assert_eq!(
+        messages,
+        vec![SubMsg::new(create_set_denom_metadata_msg(
+            &Addr::unchecked(CONTRACT_ADDRESS),
+            &sol_denom_metadata("Solana on Injective", "https://solana.com/logo.png")
+        ))],
+        "incorrect set metadata message for CW-20 with logo URL"
+    );
+
+    let messages = update_metadata(WasmMockQuerier {
+        smart_query_handler: create_cw20_marketing_query_handler(Some(LogoInfo::Embedded)),
+        ..Default::default()
+    });
+    assert_eq!(
+        messages,
+        vec![SubMsg::new(create_set_denom_metadata_msg(
+            &Addr::unchecked(CONTRACT_ADDRESS),
+            &sol_denom_metadata("Solana on Injective", "data:image/png;base64,bG9nbw==")
+        ))],
+        "incorrect set metadata message for CW-20 with embedded logo"
+    );
+}
+
+#[test]
+fn it_tries_to_update_not_registered_contract() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    let err_response = handle_update_metadata(deps.as_mut(), env, Addr::unchecked(CW_20_ADDRESS)).unwrap_err();
+    assert_eq!(err_response, ContractError::ContractNotRegistered, "incorrect error");
+}
+
+fn refresh(
+    deps: &mut OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> RefreshMetadataResponse {
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let response = handle_refresh_all_metadata(deps.as_mut(), env, mock_info(SENDER, &[]), start_after, limit).unwrap();
+    let data: RefreshMetadataResponse = from_binary(&response.data.unwrap()).unwrap();
+    assert_eq!(response.messages.len(), data.updated.len(), "one message expected per updated token");
+    data
+}
+
+#[test]
+fn it_refreshes_changed_metadata_page_by_page() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, OTHER_CW20).unwrap();
+
+    let page = refresh(&mut deps, None, Some(1));
+    assert_eq!(
+        page,
+        RefreshMetadataResponse {
+            updated: vec![OTHER_CW20.to_string()],
+            next_start_after: Some(OTHER_CW20.to_string()),
+        },
+        "incorrect first page"
+    );
+    let page = refresh(&mut deps, page.next_start_after, Some(1));
+    assert_eq!(
+        page,
+        RefreshMetadataResponse {
+            updated: vec![CW_20_ADDRESS.to_string()],
+            next_start_after: None,
+        },
+        "incorrect last page"
+    );
+
+    let page = refresh(&mut deps, None, None);
+    assert!(page.updated.is_empty(), "unchanged metadata was set again");
+
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_marketing_query_handler(None),
+        ..Default::default()
+    };
+    let page = refresh(&mut deps, None, None);
+    assert_eq!(
+        page.updated,
+        vec![OTHER_CW20.to_string(), CW_20_ADDRESS.to_string()],
+        "changed metadata was not set"
+    );
+}
+
+#[test]
+fn it_refreshes_stale_metadata_on_wrap() {
     let mut deps = mock_dependencies();
-    deps.querier = WasmMockQuerier {
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
         smart_query_handler: create_cw20_info_query_handler(),
         ..Default::default()
     };
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                metadata_refresh_blocks: 100,
+                ..Default::default()
+            },
+        )
+        .unwrap();
     let mut env = mock_env();
     env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let registered_at = env.block.height;
+    let mut wrap_at = |deps: &mut OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>, height: u64| {
+        env.block.height = height;
+        let response = handle_on_received_cw20_funds_msg(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CW_20_ADDRESS, &[]),
+            SENDER.to_string(),
+            Uint128::new(10),
+        )
+        .unwrap();
+        confirm_submessages(deps, &env, &response);
+        response.messages
+    };
+
+    assert_eq!(
+        wrap_at(&mut deps, registered_at).len(),
+        3,
+        "registration should create denom, set metadata and mint"
+    );
+
+    deps.querier.base.smart_query_handler = create_cw20_marketing_query_handler(None);
+    assert_eq!(
+        wrap_at(&mut deps, registered_at + 99).len(),
+        1,
+        "metadata was refreshed within the window"
+    );
+
+    let messages = wrap_at(&mut deps, registered_at + 100);
+    assert_eq!(
+        messages.last(),
+        Some(&SubMsg::new(create_set_denom_metadata_msg(
+            &Addr::unchecked(CONTRACT_ADDRESS),
+            &sol_denom_metadata("Solana on Injective", "")
+        ))),
+        "stale metadata was not refreshed"
+    );
+
+    assert_eq!(wrap_at(&mut deps, registered_at + 200).len(), 1, "unchanged metadata was set again");
+}
+
+#[test]
+fn it_applies_metadata_override_and_keeps_history() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_marketing_query_handler(None),
+        ..Default::default()
+    };
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
     CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let height = env.block.height;
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+
+    handle_update_metadata(deps.as_mut(), env.clone(), cw20.clone()).unwrap();
+
+    let metadata_override = MetadataOverride {
+        name: Some("Wrapped Solana".to_string()),
+        symbol: Some("wSOL".to_string()),
+        uri: Some("https://solana.com/logo.png".to_string()),
+        ..Default::default()
+    };
+    env.block.height = height + 1;
+    let response = handle_set_metadata_override(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        cw20.clone(),
+        Some(metadata_override.clone()),
+    )
+    .unwrap();
+    let mut overridden = sol_denom_metadata("Solana on Injective", "https://solana.com/logo.png");
+    overridden.name = "Wrapped Solana".to_string();
+    overridden.symbol = "wSOL".to_string();
+    overridden.display = "wSOL".to_string();
+    overridden.denom_units[1].denom = "wSOL".to_string();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(create_set_denom_metadata_msg(
+            &Addr::unchecked(CONTRACT_ADDRESS),
+            &overridden
+        ))],
+        "override was not applied"
+    );
 
-    let response = handle_update_metadata(deps.as_mut(), env, Addr::unchecked(CW_20_ADDRESS)).unwrap();
-    assert_eq!(response.messages.len(), 1, "incorrect number of messages returned");
-
-    if let SubMsg {
-        msg: CosmosMsg::Custom(InjectiveMsgWrapper { route, msg_data }),
-        ..
-    } = response.messages.get(0).unwrap()
-    {
-        assert_eq!(route, &InjectiveRoute::Tokenfactory, "submessage had wrong route");
-        if let InjectiveMsg::SetTokenMetadata {
-            denom,
-            name,
-            symbol,
-            decimals,
-        } = msg_data
-        {
-            assert_eq!(
-                get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS)),
-                denom.as_str(),
-                "incorrect denom in set metadata message"
-            );
-            assert_eq!("SOL", symbol.as_str(), "incorrect symbol in set metadata message");
-            assert_eq!("Solana", name.as_str(), "incorrect name in set metadata message");
-            assert_eq!(6, *decimals, "incorrect decimals in set metadata message");
-        } else {
-            panic!("incorrect injective message found")
-        }
-    } else {
-        panic!("incorrect submessage type found")
-    }
+    // override keeps winning over CW-20 values on later updates
+    let response = handle_update_metadata(deps.as_mut(), env.clone(), cw20.clone()).unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(create_set_denom_metadata_msg(
+            &Addr::unchecked(CONTRACT_ADDRESS),
+            &overridden
+        ))],
+        "override was not applied on update"
+    );
+    let stored: Option<MetadataOverride> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MetadataOverride {
+                addr: CW_20_ADDRESS.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(stored, Some(metadata_override), "incorrect stored override");
+
+    env.block.height = height + 2;
+    handle_set_metadata_override(deps.as_mut(), env, mock_info(ADMIN, &[]), cw20, None).unwrap();
+
+    let history: Vec<MetadataChange> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MetadataHistory {
+                addr: CW_20_ADDRESS.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let original = sol_denom_metadata("Solana on Injective", "");
+    assert_eq!(
+        history,
+        vec![
+            MetadataChange {
+                seq: 0,
+                height,
+                source: MetadataSource::Cw20,
+                metadata: original.clone(),
+            },
+            MetadataChange {
+                seq: 1,
+                height: height + 1,
+                source: MetadataSource::Override,
+                metadata: overridden,
+            },
+            MetadataChange {
+                seq: 2,
+                height: height + 2,
+                source: MetadataSource::Cw20,
+                metadata: original,
+            },
+        ],
+        "incorrect metadata history"
+    );
+
+    let page: Vec<MetadataChange> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MetadataHistory {
+                addr: CW_20_ADDRESS.to_string(),
+                start_after: Some(0),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        page.iter().map(|c| c.seq).collect::<Vec<u64>>(),
+        vec![1],
+        "incorrect page of metadata history"
+    );
 }
 
 #[test]
-fn it_tries_to_update_not_registered_contract() {
+fn it_removes_metadata_override_without_values() {
     let mut deps = mock_dependencies();
-    let env = mock_env();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_marketing_query_handler(None),
+        ..Default::default()
+    };
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+    let metadata_override = MetadataOverride {
+        uri: Some("https://solana.com/logo.png".to_string()),
+        ..Default::default()
+    };
+    handle_set_metadata_override(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), cw20.clone(), Some(metadata_override)).unwrap();
 
-    let err_response = handle_update_metadata(deps.as_mut(), env, Addr::unchecked(CW_20_ADDRESS)).unwrap_err();
-    assert_eq!(err_response, ContractError::ContractNotRegistered, "incorrect error");
+    let response = handle_set_metadata_override(deps.as_mut(), env, mock_info(ADMIN, &[]), cw20, Some(MetadataOverride::default())).unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(create_set_denom_metadata_msg(
+            &Addr::unchecked(CONTRACT_ADDRESS),
+            &sol_denom_metadata("Solana on Injective", "")
+        ))],
+        "CW-20 values were not restored"
+    );
+    let stored: Option<MetadataOverride> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::MetadataOverride {
+                addr: CW_20_ADDRESS.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(stored, None, "empty override was stored");
+}
+
+#[test]
+fn it_rejects_metadata_override_from_non_admin() {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+
+    let err = handle_set_metadata_override(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(SENDER, &[]),
+        Addr::unchecked(CW_20_ADDRESS),
+        Some(MetadataOverride::default()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "incorrect error returned");
 }
@@ -0,0 +1,220 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info},
+    Addr, Coin, Timestamp, Uint128,
+};
+
+use cw20_adapter::{
+    common::get_denom,
+    contract::query,
+    error::ContractError,
+    execute_rate_limits::handle_set_rate_limit,
+    execute_receive::handle_on_received_cw20_funds_msg,
+    execute_redeem::handle_redeem_msg,
+    msg::{QueryMsg, RateLimitResponse},
+    state::{Config, RateLimit, CONFIG, CW20_CONTRACTS, TF_SUPPLY},
+};
+
+use crate::common::{mock_dependencies, CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER};
+
+const ADMIN: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+const DAY: u64 = 86_400;
+
+#[test]
+fn it_limits_net_outflow_within_window() {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    TF_SUPPLY.save(&mut deps.storage, CW_20_ADDRESS, &Uint128::new(1_000), 0).unwrap();
+    handle_set_rate_limit(
+        deps.as_mut(),
+        mock_info(ADMIN, &[]),
+        Addr::unchecked(CW_20_ADDRESS),
+        Some(RateLimit {
+            window_seconds: DAY,
+            max_inflow_bps: None,
+            max_outflow_bps: Some(500),
+        }),
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS));
+
+    handle_redeem_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SENDER, &[Coin::new(50, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap();
+    let response = handle_redeem_msg(deps.as_mut(), env.clone(), mock_info(SENDER, &[Coin::new(1, denom.as_str())]), None, None).unwrap_err();
+    assert_eq!(response, ContractError::OutflowRateLimitExceeded, "incorrect error returned");
+
+    // wraps within the same window offset redemptions
+    handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(20),
+    )
+    .unwrap();
+    handle_redeem_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SENDER, &[Coin::new(20, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap();
+
+    let usage: RateLimitResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::RateLimit {
+                addr: CW_20_ADDRESS.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(usage.supply_at_window_start, Uint128::new(1_000), "incorrect supply at window start");
+    assert_eq!(usage.inflow, Uint128::new(20), "incorrect inflow");
+    assert_eq!(usage.outflow, Uint128::new(70), "incorrect outflow");
+
+    // usage of the previous window counts as long as part of it falls within the last day
+    env.block.time = env.block.time.plus_seconds(DAY);
+    let response = handle_redeem_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SENDER, &[Coin::new(40, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        response,
+        ContractError::OutflowRateLimitExceeded,
+        "usage of the previous window was dropped"
+    );
+
+    env.block.time = env.block.time.plus_seconds(DAY);
+    handle_redeem_msg(deps.as_mut(), env, mock_info(SENDER, &[Coin::new(40, denom.as_str())]), None, None).unwrap();
+}
+
+#[test]
+fn it_does_not_renew_quota_at_window_boundary() {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    TF_SUPPLY.save(&mut deps.storage, CW_20_ADDRESS, &Uint128::new(1_000), 0).unwrap();
+    handle_set_rate_limit(
+        deps.as_mut(),
+        mock_info(ADMIN, &[]),
+        Addr::unchecked(CW_20_ADDRESS),
+        Some(RateLimit {
+            window_seconds: DAY,
+            max_inflow_bps: None,
+            max_outflow_bps: Some(500),
+        }),
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    env.block.time = Timestamp::from_seconds(100 * DAY - 60);
+    let denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS));
+    handle_redeem_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SENDER, &[Coin::new(50, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap();
+
+    env.block.time = Timestamp::from_seconds(100 * DAY + 60);
+    let response = handle_redeem_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SENDER, &[Coin::new(10, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(response, ContractError::OutflowRateLimitExceeded, "quota was renewed at window boundary");
+
+    // half of the previous window falls within the last day
+    env.block.time = Timestamp::from_seconds(100 * DAY + DAY / 2);
+    handle_redeem_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SENDER, &[Coin::new(25, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap();
+
+    let usage: RateLimitResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::RateLimit {
+                addr: CW_20_ADDRESS.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(usage.outflow, Uint128::new(50), "incorrect outflow over the last day");
+}
+
+#[test]
+fn it_returns_error_if_rate_limit_is_invalid() {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+
+    let response = handle_set_rate_limit(
+        deps.as_mut(),
+        mock_info(ADMIN, &[]),
+        Addr::unchecked(CW_20_ADDRESS),
+        Some(RateLimit {
+            window_seconds: 0,
+            max_inflow_bps: Some(100),
+            max_outflow_bps: None,
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(response, ContractError::InvalidRateLimit, "incorrect error returned");
+}
@@ -5,15 +5,23 @@ use cosmwasm_std::{
     Addr, Coin, CosmosMsg, SubMsg, Uint128,
 };
 
-use cw20_adapter::{error::ContractError, execute_receive::handle_on_received_cw20_funds_msg, state::CW20_CONTRACTS};
-use injective_cosmwasm::{mock_dependencies, InjectiveMsg, InjectiveMsgWrapper, InjectiveRoute, WasmMockQuerier};
+use cw20_adapter::{
+    common::create_set_denom_metadata_msg,
+    error::ContractError,
+    execute_receive::handle_on_received_cw20_funds_msg,
+    state::{AutoRegistration, Config, CONFIG, CW20_CONTRACTS},
+};
+use injective_cosmwasm::{InjectiveMsg, InjectiveMsgWrapper, InjectiveRoute, WasmMockQuerier};
 
-use crate::common::{create_custom_bank_balance_query_handler, create_cw20_info_query_handler, CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER};
+use crate::common::{
+    confirm_submessages, create_custom_bank_balance_query_handler, create_cw20_info_query_handler, mock_dependencies, sol_denom_metadata,
+    CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER,
+};
 
 #[test]
 fn it_handles_receive_correctly_if_not_already_registered() {
     let mut deps = mock_dependencies();
-    deps.querier = WasmMockQuerier {
+    deps.querier.base = WasmMockQuerier {
         balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
         smart_query_handler: create_cw20_info_query_handler(),
         ..Default::default()
@@ -21,12 +29,24 @@ fn it_handles_receive_correctly_if_not_already_registered() {
     let mut env = mock_env();
     env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
     let amount_to_send = Uint128::new(100);
-    let response = handle_on_received_cw20_funds_msg(deps.as_mut(), env, mock_info(CW_20_ADDRESS, &[]), SENDER.to_string(), amount_to_send).unwrap();
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        amount_to_send,
+    )
+    .unwrap();
 
+    assert!(
+        !CW20_CONTRACTS.contains(&deps.storage, CW_20_ADDRESS),
+        "contract was registered before denom creation was confirmed"
+    );
+    confirm_submessages(&mut deps, &env, &response);
     let contract_registered = CW20_CONTRACTS.contains(&deps.storage, CW_20_ADDRESS);
     assert!(contract_registered, "contract wasn't registered");
 
-    assert_eq!(response.messages.len(), 2, "incorrect number of messages returned");
+    assert_eq!(response.messages.len(), 3, "incorrect number of messages returned");
 
     if let SubMsg {
         msg: CosmosMsg::Custom(InjectiveMsgWrapper { route, msg_data }),
@@ -43,11 +63,19 @@ fn it_handles_receive_correctly_if_not_already_registered() {
     } else {
         panic!("incorrect submessage type found")
     }
+    assert_eq!(
+        response.messages[1],
+        SubMsg::new(create_set_denom_metadata_msg(
+            &Addr::unchecked(CONTRACT_ADDRESS),
+            &sol_denom_metadata("", "")
+        )),
+        "incorrect set metadata message"
+    );
 
     if let SubMsg {
         msg: CosmosMsg::Custom(InjectiveMsgWrapper { route, msg_data }),
         ..
-    } = response.messages.get(1).unwrap()
+    } = response.messages.get(2).unwrap()
     {
         assert_eq!(route, &InjectiveRoute::Tokenfactory, "submessage had wrong route");
         if let InjectiveMsg::Mint { sender, amount, mint_to } = msg_data {
@@ -70,7 +98,7 @@ fn it_handles_receive_correctly_if_not_already_registered() {
 #[test]
 fn it_handles_receive_correctly_if_already_registered() {
     let mut deps = mock_dependencies();
-    deps.querier = WasmMockQuerier {
+    deps.querier.base = WasmMockQuerier {
         balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
         ..Default::default()
     };
@@ -111,7 +139,7 @@ fn it_handles_receive_correctly_if_already_registered() {
 #[test]
 fn it_returns_error_on_receive_if_contract_not_registered_and_contract_has_insufficient_balance() {
     let mut deps = mock_dependencies();
-    deps.querier = WasmMockQuerier {
+    deps.querier.base = WasmMockQuerier {
         balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(9, "inj")),
         ..Default::default()
     };
@@ -130,7 +158,7 @@ fn it_returns_error_on_receive_if_contract_not_registered_and_contract_has_insuf
 #[test]
 fn it_returns_error_on_receive_if_additional_funds_are_provided() {
     let mut deps = mock_dependencies();
-    deps.querier = WasmMockQuerier {
+    deps.querier.base = WasmMockQuerier {
         balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
         ..Default::default()
     };
@@ -150,3 +178,39 @@ fn it_returns_error_on_receive_if_additional_funds_are_provided() {
     assert!(!contract_registered, "contract was registered");
     assert_eq!(response, ContractError::SuperfluousFundsProvided, "funds were provided");
 }
+
+#[test]
+fn it_applies_auto_registration_policy() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    for (auto_registration, expected) in [
+        (AutoRegistration::Disabled, ContractError::AutoRegistrationDisabled),
+        (AutoRegistration::SponsoredOnly, ContractError::AutoRegistrationNotSponsored),
+    ] {
+        CONFIG
+            .save(
+                &mut deps.storage,
+                &Config {
+                    auto_registration,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let response = handle_on_received_cw20_funds_msg(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(CW_20_ADDRESS, &[]),
+            SENDER.to_string(),
+            Uint128::new(100),
+        )
+        .unwrap_err();
+        assert_eq!(response, expected, "incorrect error returned");
+        assert!(!CW20_CONTRACTS.contains(&deps.storage, CW_20_ADDRESS), "contract was registered");
+    }
+}
@@ -5,9 +5,9 @@ use cosmwasm_std::{
 };
 use cw20::Cw20ExecuteMsg;
 use cw20_adapter::{error::ContractError, execute_redeem::handle_redeem_msg, state::CW20_CONTRACTS};
-use injective_cosmwasm::{mock_dependencies, InjectiveMsg, InjectiveMsgWrapper, InjectiveRoute};
+use injective_cosmwasm::{InjectiveMsg, InjectiveMsgWrapper, InjectiveRoute};
 
-use crate::common::{CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER};
+use crate::common::{mock_dependencies, CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER};
 
 mod common;
 
@@ -1,17 +1,24 @@
 use cosmwasm_std::{
     testing::{mock_env, mock_info},
-    Addr, Coin, CosmosMsg, SubMsg,
+    Addr, BankMsg, Coin, CosmosMsg, SubMsg,
 };
 
-use cw20_adapter::{error::ContractError, execute_register::handle_register_msg, state::CW20_CONTRACTS};
-use injective_cosmwasm::{mock_dependencies, InjectiveMsg, InjectiveMsgWrapper, InjectiveRoute, WasmMockQuerier};
+use cw20_adapter::{
+    common::create_set_denom_metadata_msg,
+    error::ContractError,
+    execute_register::{handle_register_batch_msg, handle_register_msg},
+    state::{registrations, Config, CONFIG, CW20_CONTRACTS},
+};
+use injective_cosmwasm::{InjectiveMsg, InjectiveMsgWrapper, InjectiveRoute, WasmMockQuerier};
 
 use common::{create_cw20_failing_info_query_handler, create_cw20_info_query_handler, create_denom_creation_fee_failing_handler};
 
-use crate::common::{CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER};
+use crate::common::{confirm_submessages, mock_dependencies, sol_denom_metadata, CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER};
 
 mod common;
 
+const OTHER_CW20: &str = "inj1h0y3hssxf4vsdacfmjg720642cvpxwyqh35kpn";
+
 // const CONTRACT_ADDRESS: &str = "inj1pvrwmjuusn9wh34j7y520g8gumuy9xtlt6xtzw";
 // const CW_20_ADDRESS: &str = "inj1pjcw9hhx8kf462qtgu37p7l7shyqgpfr82r6em";
 // const SENDER: &str = "inj1n0qvel0zfmsxu3q8q23xzjvuwfxn0ydlhgyh7h";
@@ -19,7 +26,7 @@ mod common;
 #[test]
 fn it_handles_correct_register_msg_with_exact_funds() {
     let mut deps = mock_dependencies();
-    deps.querier = WasmMockQuerier {
+    deps.querier.base = WasmMockQuerier {
         smart_query_handler: create_cw20_info_query_handler(),
         ..Default::default()
     };
@@ -28,16 +35,20 @@ fn it_handles_correct_register_msg_with_exact_funds() {
     env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
     let response = handle_register_msg(
         deps.as_mut(),
-        env,
+        env.clone(),
         mock_info(SENDER, &[Coin::new(10, "inj")]),
         Addr::unchecked(CW_20_ADDRESS),
+        None,
     )
     .unwrap();
 
+    let contract_registered = CW20_CONTRACTS.contains(&deps.storage, CW_20_ADDRESS);
+    assert!(!contract_registered, "contract was registered before denom creation was confirmed");
+    confirm_submessages(&mut deps, &env, &response);
     let contract_registered = CW20_CONTRACTS.contains(&deps.storage, CW_20_ADDRESS);
     assert!(contract_registered, "contract wasn't registered");
 
-    assert_eq!(response.messages.len(), 1, "incorrect number of messages returned");
+    assert_eq!(response.messages.len(), 2, "incorrect number of messages returned");
 
     if let SubMsg {
         msg: CosmosMsg::Custom(InjectiveMsgWrapper { route, msg_data }),
@@ -54,6 +65,105 @@ fn it_handles_correct_register_msg_with_exact_funds() {
     } else {
         panic!("incorrect submessage type found")
     }
+    assert_eq!(
+        response.messages[1],
+        SubMsg::new(create_set_denom_metadata_msg(
+            &Addr::unchecked(CONTRACT_ADDRESS),
+            &sol_denom_metadata("", "")
+        )),
+        "incorrect set metadata message"
+    );
+}
+
+#[test]
+fn it_refunds_overpayment_in_fee_denom() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let response = handle_register_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SENDER, &[Coin::new(15, "inj")]),
+        Addr::unchecked(CW_20_ADDRESS),
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(response.messages.len(), 3, "incorrect number of messages returned");
+    assert_eq!(
+        response.messages[2],
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: SENDER.to_string(),
+            amount: vec![Coin::new(5, "inj")],
+        })),
+        "incorrect refund message"
+    );
+
+    let registration = registrations().load(&deps.storage, CW_20_ADDRESS).unwrap();
+    assert_eq!(registration.registrant, Addr::unchecked(SENDER), "incorrect registrant recorded");
+    assert_eq!(registration.fee_paid, vec![Coin::new(10, "inj")], "incorrect fee recorded");
+    assert_eq!(registration.registered_at_height, env.block.height, "incorrect height recorded");
+    assert_eq!(registration.symbol, "SOL", "incorrect symbol recorded");
+}
+
+#[test]
+fn it_forwards_registration_surcharge_to_treasury() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let treasury = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                treasury: Some(Addr::unchecked(treasury)),
+                registration_surcharge: vec![Coin::new(2, "inj")],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let response = handle_register_msg(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(SENDER, &[Coin::new(10, "inj")]),
+        Addr::unchecked(CW_20_ADDRESS),
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(
+        response,
+        ContractError::NotEnoughBalanceToPayDenomCreationFee,
+        "surcharge was not required"
+    );
+
+    let response = handle_register_msg(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(SENDER, &[Coin::new(12, "inj")]),
+        Addr::unchecked(CW_20_ADDRESS),
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(response.messages.len(), 3, "incorrect number of messages returned");
+    assert_eq!(
+        response.messages[2],
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: treasury.to_string(),
+            amount: vec![Coin::new(2, "inj")],
+        })),
+        "incorrect treasury message"
+    );
+    let registration = registrations().load(&deps.storage, CW_20_ADDRESS).unwrap();
+    assert_eq!(registration.fee_paid, vec![Coin::new(12, "inj")], "incorrect fee recorded");
 }
 
 #[test]
@@ -66,6 +176,7 @@ fn it_handles_correct_register_msg_with_extra_funds() {
         env,
         mock_info(SENDER, &[Coin::new(100, "inj"), Coin::new(20, "usdt")]),
         Addr::unchecked(CW_20_ADDRESS),
+        None,
     )
     .unwrap_err();
     assert_eq!(response_err, ContractError::SuperfluousFundsProvided);
@@ -74,7 +185,7 @@ fn it_handles_correct_register_msg_with_extra_funds() {
 #[test]
 fn it_handles_correct_register_msg_with_non_cannonical_cw20_address() {
     let mut deps = mock_dependencies();
-    deps.querier = WasmMockQuerier {
+    deps.querier.base = WasmMockQuerier {
         smart_query_handler: create_cw20_failing_info_query_handler(),
         ..Default::default()
     };
@@ -85,6 +196,7 @@ fn it_handles_correct_register_msg_with_non_cannonical_cw20_address() {
         mock_env(),
         mock_info(SENDER, &[Coin::new(10, "inj")]),
         Addr::unchecked(non_cannonical_address.to_string()),
+        None,
     )
     .unwrap_err();
 
@@ -101,7 +213,7 @@ fn it_returns_error_if_already_registered_register_msg() {
     let contract_address = Addr::unchecked("amazing_address");
     CW20_CONTRACTS.insert(storage, contract_address.as_str()).unwrap();
 
-    let response = handle_register_msg(deps.as_mut(), mock_env(), mock_info("sender", &[]), contract_address);
+    let response = handle_register_msg(deps.as_mut(), mock_env(), mock_info("sender", &[]), contract_address, None);
 
     assert_eq!(
         response.unwrap_err(),
@@ -113,7 +225,7 @@ fn it_returns_error_if_already_registered_register_msg() {
 #[test]
 fn it_returns_error_if_cannot_query_denom_creation_fee_register_msg() {
     let mut deps = mock_dependencies();
-    deps.querier = WasmMockQuerier {
+    deps.querier.base = WasmMockQuerier {
         token_factory_denom_creation_fee_handler: create_denom_creation_fee_failing_handler(),
         ..Default::default()
     };
@@ -123,6 +235,7 @@ fn it_returns_error_if_cannot_query_denom_creation_fee_register_msg() {
         mock_env(),
         mock_info(SENDER, &[Coin::new(10, "inj")]),
         Addr::unchecked(CW_20_ADDRESS),
+        None,
     )
     .unwrap_err();
 
@@ -140,6 +253,7 @@ fn it_returns_error_if_mismatched_denom_is_passed_register_msg() {
         mock_env(),
         mock_info(SENDER, &[Coin::new(10, "usdt")]),
         Addr::unchecked(CW_20_ADDRESS),
+        None,
     )
     .unwrap_err();
 
@@ -158,6 +272,7 @@ fn it_returns_error_if_insufficient_coins_are_passed_register_msg() {
         mock_env(),
         mock_info(SENDER, &[Coin::new(9, "inj")]),
         Addr::unchecked(CW_20_ADDRESS),
+        None,
     )
     .unwrap_err();
 
@@ -170,7 +285,7 @@ fn it_returns_error_if_insufficient_coins_are_passed_register_msg() {
 #[test]
 fn it_returns_error_if_no_coins_are_passed_register_msg() {
     let mut deps = mock_dependencies();
-    let response = handle_register_msg(deps.as_mut(), mock_env(), mock_info(SENDER, &[]), Addr::unchecked(CW_20_ADDRESS)).unwrap_err();
+    let response = handle_register_msg(deps.as_mut(), mock_env(), mock_info(SENDER, &[]), Addr::unchecked(CW_20_ADDRESS), None).unwrap_err();
 
     assert_eq!(response, ContractError::NotEnoughBalanceToPayDenomCreationFee, "incorrect error returned");
 
@@ -181,7 +296,7 @@ fn it_returns_error_if_no_coins_are_passed_register_msg() {
 #[test]
 fn it_returns_error_if_register_is_not_cw20_msg() {
     let mut deps = mock_dependencies();
-    deps.querier = WasmMockQuerier {
+    deps.querier.base = WasmMockQuerier {
         smart_query_handler: create_cw20_failing_info_query_handler(),
         ..Default::default()
     };
@@ -191,6 +306,7 @@ fn it_returns_error_if_register_is_not_cw20_msg() {
         mock_env(),
         mock_info(SENDER, &[Coin::new(10, "inj")]),
         Addr::unchecked(CW_20_ADDRESS),
+        None,
     )
     .unwrap_err();
 
@@ -199,3 +315,100 @@ fn it_returns_error_if_register_is_not_cw20_msg() {
     let contract_registered = CW20_CONTRACTS.contains(&deps.storage, CW_20_ADDRESS);
     assert!(!contract_registered, "contract was registered");
 }
+
+#[test]
+fn it_registers_batch_of_contracts_for_fee_per_contract() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let treasury = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                treasury: Some(Addr::unchecked(treasury)),
+                registration_surcharge: vec![Coin::new(2, "inj")],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let addrs = vec![Addr::unchecked(CW_20_ADDRESS), Addr::unchecked(OTHER_CW20)];
+
+    let err = handle_register_batch_msg(deps.as_mut(), mock_env(), mock_info(SENDER, &[Coin::new(12, "inj")]), addrs.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotEnoughBalanceToPayDenomCreationFee,
+        "fee was not required per contract"
+    );
+
+    let err = handle_register_batch_msg(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(SENDER, &[Coin::new(24, "inj"), Coin::new(1, "usdt")]),
+        addrs.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::SuperfluousFundsProvided, "funds in other denoms were accepted");
+
+    let response = handle_register_batch_msg(deps.as_mut(), mock_env(), mock_info(SENDER, &[Coin::new(25, "inj")]), addrs).unwrap();
+    confirm_submessages(&mut deps, &mock_env(), &response);
+    assert_eq!(response.messages.len(), 6, "incorrect number of messages returned");
+    assert_eq!(response.events.len(), 2, "one register event expected per contract");
+    assert_eq!(
+        response.messages[4..].to_vec(),
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: SENDER.to_string(),
+                amount: vec![Coin::new(1, "inj")],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: treasury.to_string(),
+                amount: vec![Coin::new(4, "inj")],
+            })),
+        ],
+        "incorrect refund and treasury messages"
+    );
+    for addr in [CW_20_ADDRESS, OTHER_CW20] {
+        assert!(CW20_CONTRACTS.contains(&deps.storage, addr), "contract wasn't registered");
+        let registration = registrations().load(&deps.storage, addr).unwrap();
+        assert_eq!(registration.fee_paid, vec![Coin::new(12, "inj")], "incorrect fee recorded");
+    }
+}
+
+#[test]
+fn it_rejects_batch_with_duplicated_or_registered_contracts() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let new_cw20 = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+    CW20_CONTRACTS.insert(&mut deps.storage, OTHER_CW20).unwrap();
+
+    let err = handle_register_batch_msg(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(SENDER, &[Coin::new(40, "inj")]),
+        vec![
+            Addr::unchecked(CW_20_ADDRESS),
+            Addr::unchecked(OTHER_CW20),
+            Addr::unchecked(CW_20_ADDRESS),
+            Addr::unchecked(new_cw20),
+        ],
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BatchRegistrationRejected {
+            duplicated: vec![CW_20_ADDRESS.to_string()],
+            already_registered: vec![OTHER_CW20.to_string()],
+        },
+        "incorrect error returned"
+    );
+    assert!(!CW20_CONTRACTS.contains(&deps.storage, CW_20_ADDRESS), "contract was registered");
+
+    let err = handle_register_batch_msg(deps.as_mut(), mock_env(), mock_info(SENDER, &[]), vec![]).unwrap_err();
+    assert_eq!(err, ContractError::NoContractsProvided, "incorrect error returned");
+}
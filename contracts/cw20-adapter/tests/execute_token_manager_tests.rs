@@ -0,0 +1,256 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info, MockApi, MockStorage},
+    Addr, HexBinary, OwnedDeps, Uint128,
+};
+
+use cw20_adapter::{
+    contract::query,
+    error::ContractError,
+    execute_limits::handle_set_token_limits,
+    execute_metadata::handle_set_metadata_override,
+    execute_token_manager::{handle_claim_token_manager, handle_set_token_manager},
+    execute_token_restrictions::handle_set_token_frozen,
+    msg::QueryMsg,
+    state::{CodePin, Config, MetadataOverride, TokenLimits, CODE_PINS, CONFIG, CW20_CONTRACTS},
+};
+use injective_cosmwasm::{InjectiveQueryWrapper, WasmMockQuerier};
+
+use crate::common::{
+    create_cw20_info_query_handler, create_cw20_minter_query_handler, mock_dependencies, AdapterMockQuerier, CONTRACT_ADDRESS, CW_20_ADDRESS,
+    CW_20_CODE_ID, SENDER,
+};
+
+const ADMIN: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+const MANAGER: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+const OTHER_CW20: &str = "inj1h0y3hssxf4vsdacfmjg720642cvpxwyqh35kpn";
+
+type Deps = OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>;
+
+fn setup() -> Deps {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, OTHER_CW20).unwrap();
+    deps
+}
+
+fn token_manager(deps: &Deps) -> Option<Addr> {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::TokenManager {
+                addr: CW_20_ADDRESS.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn it_lets_cw20_minter_or_admin_claim_manager_role() {
+    let mut deps = setup();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_minter_query_handler(MANAGER),
+        ..Default::default()
+    };
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+
+    let err = handle_claim_token_manager(deps.as_mut(), mock_info(SENDER, &[]), cw20.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "claimed by address without CW-20 role");
+
+    handle_claim_token_manager(deps.as_mut(), mock_info(MANAGER, &[]), cw20.clone()).unwrap();
+    assert_eq!(token_manager(&deps), Some(Addr::unchecked(MANAGER)), "minter did not become manager");
+
+    let err = handle_claim_token_manager(deps.as_mut(), mock_info(MANAGER, &[]), cw20.clone()).unwrap_err();
+    assert_eq!(err, ContractError::TokenManagerAlreadySet, "claimed token with a manager");
+
+    handle_set_token_manager(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), None).unwrap();
+    assert_eq!(token_manager(&deps), None, "manager was not revoked");
+
+    deps.querier.contract_admin = Some(SENDER.to_string());
+    handle_claim_token_manager(deps.as_mut(), mock_info(SENDER, &[]), cw20).unwrap();
+    assert_eq!(token_manager(&deps), Some(Addr::unchecked(SENDER)), "wasm admin did not become manager");
+}
+
+#[test]
+fn it_lets_only_admin_hand_manager_role_over() {
+    let mut deps = setup();
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+
+    let err = handle_set_token_manager(deps.as_mut(), mock_info(SENDER, &[]), cw20.clone(), Some(MANAGER.to_string())).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "incorrect error returned");
+
+    handle_set_token_manager(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), Some(SENDER.to_string())).unwrap();
+    let err = handle_set_token_manager(deps.as_mut(), mock_info(SENDER, &[]), cw20.clone(), Some(MANAGER.to_string())).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "manager handed the role over");
+
+    handle_set_token_manager(deps.as_mut(), mock_info(ADMIN, &[]), cw20, Some(MANAGER.to_string())).unwrap();
+    assert_eq!(token_manager(&deps), Some(Addr::unchecked(MANAGER)), "role was not handed over");
+}
+
+#[test]
+fn it_scopes_manager_permissions_to_its_token() {
+    let mut deps = setup();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+    let other_cw20 = Addr::unchecked(OTHER_CW20);
+    handle_set_token_manager(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), Some(MANAGER.to_string())).unwrap();
+    let limits = TokenLimits {
+        max_wrap: Some(Uint128::new(100)),
+        ..Default::default()
+    };
+
+    handle_set_token_limits(deps.as_mut(), mock_info(MANAGER, &[]), cw20.clone(), limits.clone()).unwrap();
+    handle_set_metadata_override(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MANAGER, &[]),
+        cw20.clone(),
+        Some(MetadataOverride::default()),
+    )
+    .unwrap();
+    handle_set_token_frozen(deps.as_mut(), mock_info(MANAGER, &[]), cw20.clone(), true).unwrap();
+    handle_set_token_frozen(deps.as_mut(), mock_info(MANAGER, &[]), cw20.clone(), false).unwrap();
+
+    let err = handle_set_token_limits(deps.as_mut(), mock_info(MANAGER, &[]), other_cw20.clone(), limits).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "manager set limits of another token");
+    let err = handle_set_metadata_override(deps.as_mut(), env, mock_info(MANAGER, &[]), other_cw20.clone(), None).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "manager set metadata override of another token");
+    let err = handle_set_token_frozen(deps.as_mut(), mock_info(MANAGER, &[]), other_cw20, true).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "manager froze another token");
+}
+
+#[test]
+fn it_keeps_supply_cap_with_admin() {
+    let mut deps = setup();
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+    handle_set_token_manager(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), Some(MANAGER.to_string())).unwrap();
+    let capped = TokenLimits {
+        supply_cap: Some(Uint128::new(1000)),
+        ..Default::default()
+    };
+    handle_set_token_limits(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), capped.clone()).unwrap();
+
+    for supply_cap in [None, Some(Uint128::new(2000)), Some(Uint128::new(500))] {
+        let limits = TokenLimits {
+            supply_cap,
+            ..Default::default()
+        };
+        let err = handle_set_token_limits(deps.as_mut(), mock_info(MANAGER, &[]), cw20.clone(), limits).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized, "manager changed supply cap to {supply_cap:?}");
+    }
+
+    let limits = TokenLimits {
+        max_wrap: Some(Uint128::new(100)),
+        ..capped
+    };
+    handle_set_token_limits(deps.as_mut(), mock_info(MANAGER, &[]), cw20, limits).unwrap();
+}
+
+#[test]
+fn it_keeps_name_and_symbol_overrides_with_admin() {
+    let mut deps = setup();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+    handle_set_token_manager(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), Some(MANAGER.to_string())).unwrap();
+
+    for metadata_override in [
+        MetadataOverride {
+            symbol: Some("USDT".to_string()),
+            ..Default::default()
+        },
+        MetadataOverride {
+            name: Some("Tether".to_string()),
+            ..Default::default()
+        },
+    ] {
+        let err =
+            handle_set_metadata_override(deps.as_mut(), env.clone(), mock_info(MANAGER, &[]), cw20.clone(), Some(metadata_override)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized, "manager overrode token labels");
+    }
+
+    let admin_override = MetadataOverride {
+        symbol: Some("wSOL".to_string()),
+        ..Default::default()
+    };
+    handle_set_metadata_override(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(ADMIN, &[]),
+        cw20.clone(),
+        Some(admin_override.clone()),
+    )
+    .unwrap();
+    let err = handle_set_metadata_override(deps.as_mut(), env.clone(), mock_info(MANAGER, &[]), cw20.clone(), None).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "manager removed admin symbol override");
+
+    let manager_override = MetadataOverride {
+        description: Some("Wrapped Solana".to_string()),
+        ..admin_override
+    };
+    handle_set_metadata_override(deps.as_mut(), env, mock_info(MANAGER, &[]), cw20, Some(manager_override)).unwrap();
+}
+
+#[test]
+fn it_keeps_admin_freeze_with_admin() {
+    let mut deps = setup();
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+    handle_set_token_manager(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), Some(MANAGER.to_string())).unwrap();
+    handle_set_token_frozen(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), true).unwrap();
+
+    let err = handle_set_token_frozen(deps.as_mut(), mock_info(MANAGER, &[]), cw20.clone(), false).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "manager lifted admin freeze");
+    handle_set_token_frozen(deps.as_mut(), mock_info(MANAGER, &[]), cw20.clone(), true).unwrap();
+    let err = handle_set_token_frozen(deps.as_mut(), mock_info(MANAGER, &[]), cw20.clone(), false).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "manager freezing again took over admin freeze");
+
+    handle_set_token_frozen(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), false).unwrap();
+    handle_set_token_frozen(deps.as_mut(), mock_info(MANAGER, &[]), cw20.clone(), true).unwrap();
+    handle_set_token_frozen(deps.as_mut(), mock_info(MANAGER, &[]), cw20, false).unwrap();
+}
+
+#[test]
+fn it_keeps_migrated_token_review_with_admin() {
+    let mut deps = setup();
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+    handle_set_token_manager(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), Some(MANAGER.to_string())).unwrap();
+    CODE_PINS
+        .save(
+            &mut deps.storage,
+            CW_20_ADDRESS,
+            &CodePin {
+                code_id: CW_20_CODE_ID + 1,
+                checksum: HexBinary::from(vec![0u8; 32]),
+            },
+        )
+        .unwrap();
+    handle_set_token_frozen(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), true).unwrap();
+
+    let err = handle_set_token_frozen(deps.as_mut(), mock_info(MANAGER, &[]), cw20.clone(), false).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "manager unfroze token running unpinned code");
+
+    handle_set_token_frozen(deps.as_mut(), mock_info(ADMIN, &[]), cw20, false).unwrap();
+}
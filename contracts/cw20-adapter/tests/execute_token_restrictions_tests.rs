@@ -0,0 +1,381 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary, from_slice,
+    testing::{mock_env, mock_info, MockApi, MockStorage},
+    to_binary, Addr, Coin, CosmosMsg, Env, HexBinary, OwnedDeps, Reply, SubMsg, SubMsgResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use cw20_adapter::{
+    common::{create_set_before_send_hook_msg, get_denom},
+    contract::{query, reply, sudo},
+    error::ContractError,
+    execute_access_list::handle_update_blocklist,
+    execute_receive::handle_on_received_cw20_funds_msg,
+    execute_redeem::handle_redeem_msg,
+    execute_token_restrictions::{handle_block_before_send, handle_set_send_hook, handle_set_token_frozen},
+    msg::{QueryMsg, SudoMsg, TokenRestrictionsResponse},
+    state::{CodePin, Config, CODE_PINS, CONFIG, CW20_CONTRACTS},
+};
+use injective_cosmwasm::{InjectiveQueryWrapper, WasmMockQuerier};
+
+use crate::common::{
+    create_custom_bank_balance_query_handler, create_cw20_info_query_handler, mock_dependencies, AdapterMockQuerier, CONTRACT_ADDRESS, CW_20_ADDRESS,
+    CW_20_CHECKSUM, CW_20_CODE_ID, SENDER,
+};
+
+const ADMIN: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+const RECIPIENT: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+
+fn setup() -> (OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>, Env) {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    (deps, env)
+}
+
+fn restrictions(deps: &OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>) -> TokenRestrictionsResponse {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::TokenRestrictions {
+                addr: CW_20_ADDRESS.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn code_pin(deps: &OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>) -> Option<CodePin> {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::CodePin {
+                addr: CW_20_ADDRESS.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn it_refuses_wrap_and_redeem_of_frozen_token() {
+    let (mut deps, env) = setup();
+    handle_set_token_frozen(deps.as_mut(), mock_info(ADMIN, &[]), Addr::unchecked(CW_20_ADDRESS), true).unwrap();
+    assert!(restrictions(&deps).frozen, "token should be frozen");
+
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(10),
+    )
+    .unwrap_err();
+    assert_eq!(response, ContractError::TokenFrozen, "frozen token was wrapped");
+
+    let denom = get_denom(&env.contract.address, &Addr::unchecked(CW_20_ADDRESS));
+    let response = handle_redeem_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SENDER, &[Coin::new(10, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap_err();
+    assert_eq!(response, ContractError::TokenFrozen, "frozen token was redeemed");
+
+    handle_set_token_frozen(deps.as_mut(), mock_info(ADMIN, &[]), Addr::unchecked(CW_20_ADDRESS), false).unwrap();
+    handle_on_received_cw20_funds_msg(deps.as_mut(), env, mock_info(CW_20_ADDRESS, &[]), SENDER.to_string(), Uint128::new(10)).unwrap();
+}
+
+#[test]
+fn it_allows_only_admin_to_change_restrictions() {
+    let (mut deps, env) = setup();
+
+    let response = handle_set_token_frozen(deps.as_mut(), mock_info(SENDER, &[]), Addr::unchecked(CW_20_ADDRESS), true).unwrap_err();
+    assert_eq!(response, ContractError::Unauthorized, "incorrect error returned");
+
+    let response = handle_set_send_hook(deps.as_mut(), env, mock_info(SENDER, &[]), Addr::unchecked(CW_20_ADDRESS), true).unwrap_err();
+    assert_eq!(response, ContractError::Unauthorized, "incorrect error returned");
+}
+
+#[test]
+fn it_sets_and_clears_before_send_hook() {
+    let (mut deps, env) = setup();
+    let denom = get_denom(&env.contract.address, &Addr::unchecked(CW_20_ADDRESS));
+
+    let response = handle_set_send_hook(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), Addr::unchecked(CW_20_ADDRESS), true).unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(create_set_before_send_hook_msg(
+            &env.contract.address,
+            &denom,
+            CONTRACT_ADDRESS
+        ))],
+        "incorrect hook message"
+    );
+    assert!(restrictions(&deps).send_hook_enabled, "send hook should be enabled");
+
+    let response = handle_set_send_hook(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), Addr::unchecked(CW_20_ADDRESS), false).unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::new(create_set_before_send_hook_msg(&env.contract.address, &denom, ""))],
+        "incorrect hook message"
+    );
+    assert!(!restrictions(&deps).send_hook_enabled, "send hook should be disabled");
+}
+
+#[test]
+fn it_blocks_transfers_of_frozen_tokens_and_blocked_addresses() {
+    let (mut deps, env) = setup();
+    let denom = get_denom(&env.contract.address, &Addr::unchecked(CW_20_ADDRESS));
+    handle_set_send_hook(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), Addr::unchecked(CW_20_ADDRESS), true).unwrap();
+
+    handle_block_before_send(
+        deps.as_mut(),
+        env.clone(),
+        SENDER.to_string(),
+        RECIPIENT.to_string(),
+        Coin::new(10, denom.as_str()),
+    )
+    .unwrap();
+
+    handle_update_blocklist(deps.as_mut(), mock_info(ADMIN, &[]), vec![RECIPIENT.to_string()], vec![]).unwrap();
+    let response = handle_block_before_send(
+        deps.as_mut(),
+        env.clone(),
+        SENDER.to_string(),
+        RECIPIENT.to_string(),
+        Coin::new(10, denom.as_str()),
+    )
+    .unwrap_err();
+    assert_eq!(
+        response,
+        ContractError::AddressBlocked {
+            address: RECIPIENT.to_string()
+        },
+        "transfer to blocked address was allowed"
+    );
+
+    handle_set_token_frozen(deps.as_mut(), mock_info(ADMIN, &[]), Addr::unchecked(CW_20_ADDRESS), true).unwrap();
+    let response = handle_block_before_send(
+        deps.as_mut(),
+        env.clone(),
+        SENDER.to_string(),
+        ADMIN.to_string(),
+        Coin::new(10, denom.as_str()),
+    )
+    .unwrap_err();
+    assert_eq!(response, ContractError::TokenFrozen, "transfer of frozen token was allowed");
+
+    // adapter itself still moves tokens when minting and burning
+    handle_block_before_send(
+        deps.as_mut(),
+        env,
+        CONTRACT_ADDRESS.to_string(),
+        RECIPIENT.to_string(),
+        Coin::new(10, denom.as_str()),
+    )
+    .unwrap();
+}
+
+#[test]
+fn it_accepts_track_before_send() {
+    let (mut deps, env) = setup();
+    let denom = get_denom(&env.contract.address, &Addr::unchecked(CW_20_ADDRESS));
+    handle_set_send_hook(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]), Addr::unchecked(CW_20_ADDRESS), true).unwrap();
+    handle_set_token_frozen(deps.as_mut(), mock_info(ADMIN, &[]), Addr::unchecked(CW_20_ADDRESS), true).unwrap();
+
+    let msg: SudoMsg = from_slice(
+        format!(
+            r#"{{"track_before_send":{{"from":"{}","to":"{}","amount":{{"denom":"{}","amount":"10"}}}}}}"#,
+            SENDER, RECIPIENT, denom
+        )
+        .as_bytes(),
+    )
+    .unwrap();
+    let response = sudo(deps.as_mut(), env, msg).unwrap();
+    assert!(
+        response.messages.is_empty() && response.events.is_empty(),
+        "tracking call was not ignored"
+    );
+}
+
+#[test]
+fn it_ignores_tokens_without_send_hook() {
+    let (mut deps, env) = setup();
+    let denom = get_denom(&env.contract.address, &Addr::unchecked(CW_20_ADDRESS));
+    handle_set_token_frozen(deps.as_mut(), mock_info(ADMIN, &[]), Addr::unchecked(CW_20_ADDRESS), true).unwrap();
+
+    handle_block_before_send(
+        deps.as_mut(),
+        env.clone(),
+        SENDER.to_string(),
+        RECIPIENT.to_string(),
+        Coin::new(10, denom.as_str()),
+    )
+    .unwrap();
+    handle_block_before_send(deps.as_mut(), env, SENDER.to_string(), RECIPIENT.to_string(), Coin::new(10, "inj")).unwrap();
+}
+
+#[test]
+fn it_pins_code_at_registration() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+
+    handle_on_received_cw20_funds_msg(deps.as_mut(), env, mock_info(CW_20_ADDRESS, &[]), SENDER.to_string(), Uint128::new(10)).unwrap();
+
+    assert_eq!(
+        code_pin(&deps),
+        Some(CodePin {
+            code_id: CW_20_CODE_ID,
+            checksum: HexBinary::from_hex(CW_20_CHECKSUM).unwrap(),
+        }),
+        "incorrect code pinned"
+    );
+}
+
+#[test]
+fn it_freezes_token_and_returns_deposit_after_migration() {
+    let (mut deps, env) = setup();
+    handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(10),
+    )
+    .unwrap();
+    assert!(code_pin(&deps).is_some(), "token registered before pinning should get pinned on wrap");
+
+    deps.querier.code_id = CW_20_CODE_ID + 1;
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(10),
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages,
+        vec![SubMsg::reply_always(
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: CW_20_ADDRESS.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: SENDER.to_string(),
+                    amount: Uint128::new(10),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            response.messages[0].id,
+        )],
+        "deposit should be returned instead of minting"
+    );
+    assert!(restrictions(&deps).frozen, "migrated token should be frozen");
+    assert_eq!(code_pin(&deps).unwrap().code_id, CW_20_CODE_ID, "pin should not change before review");
+
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(10),
+    )
+    .unwrap_err();
+    assert_eq!(response, ContractError::TokenFrozen, "incorrect error returned");
+
+    // admin accepts the new code by unfreezing
+    handle_set_token_frozen(deps.as_mut(), mock_info(ADMIN, &[]), Addr::unchecked(CW_20_ADDRESS), false).unwrap();
+    assert_eq!(code_pin(&deps).unwrap().code_id, CW_20_CODE_ID + 1, "new code was not pinned");
+    handle_on_received_cw20_funds_msg(deps.as_mut(), env, mock_info(CW_20_ADDRESS, &[]), SENDER.to_string(), Uint128::new(10)).unwrap();
+}
+
+#[test]
+fn it_keeps_token_frozen_when_migrated_cw20_rejects_refund() {
+    let (mut deps, env) = setup();
+    handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(10),
+    )
+    .unwrap();
+
+    deps.querier.code_id = CW_20_CODE_ID + 1;
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(10),
+    )
+    .unwrap();
+    reply(
+        deps.as_mut(),
+        env,
+        Reply {
+            id: response.messages[0].id,
+            result: SubMsgResult::Err("transfers disabled".to_string()),
+        },
+    )
+    .unwrap();
+
+    assert!(restrictions(&deps).frozen, "migrated token should stay frozen");
+    let claim: Uint128 = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::RedemptionClaim {
+                addr: CW_20_ADDRESS.to_string(),
+                claimant: SENDER.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(claim, Uint128::new(10), "rejected refund should be left as a claim");
+}
+
+#[test]
+fn it_freezes_token_on_checksum_mismatch() {
+    let (mut deps, env) = setup();
+    CODE_PINS
+        .save(
+            &mut deps.storage,
+            CW_20_ADDRESS,
+            &CodePin {
+                code_id: CW_20_CODE_ID,
+                checksum: HexBinary::from(vec![1u8; 32]),
+            },
+        )
+        .unwrap();
+
+    handle_on_received_cw20_funds_msg(deps.as_mut(), env, mock_info(CW_20_ADDRESS, &[]), SENDER.to_string(), Uint128::new(10)).unwrap();
+    assert!(restrictions(&deps).frozen, "token with changed checksum should be frozen");
+}
@@ -3,12 +3,12 @@ mod common;
 use cosmwasm_std::testing::mock_info;
 use cosmwasm_std::{Addr, Coin, Uint128};
 use cw20_adapter::common::get_denom;
-use injective_cosmwasm::{create_simple_balance_bank_query_handler, mock_dependencies, WasmMockQuerier};
+use injective_cosmwasm::{create_simple_balance_bank_query_handler, WasmMockQuerier};
 
 use cw20_adapter::contract::{execute, instantiate};
 use cw20_adapter::msg::{ExecuteMsg, InstantiateMsg};
 
-use crate::common::{create_cw20_info_query_handler, mock_env};
+use crate::common::{confirm_submessages, create_cw20_info_query_handler, mock_dependencies, mock_env};
 
 pub const ADAPTER_CONTRACT: &str = "inj1zwv6feuzhy6a9wekh96cd57lsarmqlwxvdl4nk";
 pub const CW20_CONTRACT: &str = "inj1h0y3hssxf4vsdacfmjg720642cvpxwyqh35kpn";
@@ -22,9 +22,9 @@ fn it_can_perform_basic_operations() {
 
     wasm_querier.balance_query_handler = create_simple_balance_bank_query_handler(vec![Coin::new(10, "inj")]);
     wasm_querier.smart_query_handler = create_cw20_info_query_handler();
-    deps.querier = wasm_querier;
+    deps.querier.base = wasm_querier;
 
-    let msg = InstantiateMsg {};
+    let msg = InstantiateMsg::default();
 
     let info_inst = mock_info(ADMIN, &[]);
     let _res_inst = instantiate(deps.as_mut(), mock_env(ADAPTER_CONTRACT), info_inst, msg).unwrap();
@@ -36,7 +36,8 @@ fn it_can_perform_basic_operations() {
         amount: Uint128::new(1000),
         msg: Default::default(),
     };
-    let _res_receive = execute(deps.as_mut(), mock_env(ADAPTER_CONTRACT), info_receive, msg).unwrap();
+    let res_receive = execute(deps.as_mut(), mock_env(ADAPTER_CONTRACT), info_receive, msg).unwrap();
+    confirm_submessages(&mut deps, &mock_env(ADAPTER_CONTRACT), &res_receive);
 
     let denom = get_denom(&Addr::unchecked(ADAPTER_CONTRACT), &Addr::unchecked(CW20_CONTRACT));
     // redeem some tokens to a contract
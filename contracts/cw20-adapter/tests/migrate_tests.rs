@@ -0,0 +1,142 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info},
+    Addr, Uint128,
+};
+
+use cw20_adapter::{
+    contract::{migrate, query, CONTRACT_NAME, CONTRACT_VERSION},
+    error::ContractError,
+    execute_receive::handle_on_received_cw20_funds_msg,
+    msg::{MigrateMsg, QueryMsg},
+    state::{CodePin, Config, CONFIG, CW20_CONTRACTS},
+};
+use injective_cosmwasm::WasmMockQuerier;
+
+use crate::common::{confirm_submessages, create_cw20_info_query_handler, mock_dependencies, CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER};
+
+const ADMIN: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+
+#[test]
+fn it_migrates_adapter_without_configuration() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    // state of an adapter deployed before configuration was introduced
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.1.0").unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { admin: None }).unwrap_err();
+    assert_eq!(err, ContractError::MigrationAdminRequired, "adapter was left without admin");
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            admin: Some(ADMIN.to_string()),
+        },
+    )
+    .unwrap();
+    let config: Config = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.admin, Some(Addr::unchecked(ADMIN)), "incorrect admin set");
+    assert_eq!(
+        cw2::get_contract_version(&deps.storage).unwrap().version,
+        CONTRACT_VERSION,
+        "contract version was not updated"
+    );
+
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(10),
+    )
+    .unwrap();
+    confirm_submessages(&mut deps, &env, &response);
+    let pin: Option<CodePin> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::CodePin {
+                addr: CW_20_ADDRESS.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(pin.is_some(), "legacy token was not pinned on wrap");
+    let supply: Uint128 = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Supply {
+                addr: CW_20_ADDRESS.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(supply, Uint128::new(10), "supply of legacy token was not tracked");
+}
+
+#[test]
+fn it_keeps_configuration_on_migration() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, CONTRACT_NAME, "1.0.0").unwrap();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                metadata_refresh_blocks: 100,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg { admin: None }).unwrap();
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.admin, Some(Addr::unchecked(ADMIN)), "admin was changed");
+    assert_eq!(config.metadata_refresh_blocks, 100, "configuration was reset");
+
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            admin: Some(SENDER.to_string()),
+        },
+    )
+    .unwrap();
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.admin, Some(Addr::unchecked(SENDER)), "admin was not replaced");
+    assert_eq!(config.metadata_refresh_blocks, 100, "configuration was reset");
+}
+
+#[test]
+fn it_rejects_migration_from_other_contract() {
+    let mut deps = mock_dependencies();
+    cw2::set_contract_version(&mut deps.storage, "crates.io:cw20-base", "1.0.0").unwrap();
+
+    let err = migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            admin: Some(ADMIN.to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidMigrationSource {
+            contract: "crates.io:cw20-base".to_string()
+        },
+        "incorrect error returned"
+    );
+}
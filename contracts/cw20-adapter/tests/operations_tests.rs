@@ -0,0 +1,197 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info, MockApi, MockStorage},
+    Addr, Coin, OwnedDeps, Uint128,
+};
+
+use cw20_adapter::{
+    common::get_denom,
+    contract::query,
+    execute_receive::handle_on_received_cw20_funds_msg,
+    execute_redeem::handle_redeem_msg,
+    msg::QueryMsg,
+    operations::{record_operation, MAX_LOGGED_OPERATIONS},
+    state::{Operation, OperationKind, ADDRESS_OPERATIONS},
+};
+use injective_cosmwasm::{InjectiveQueryWrapper, WasmMockQuerier};
+
+use crate::common::{
+    confirm_submessages, create_custom_bank_balance_query_handler, create_cw20_info_query_handler, mock_dependencies, AdapterMockQuerier,
+    CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER,
+};
+
+const RECIPIENT: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+const OTHER_CW20: &str = "inj1h0y3hssxf4vsdacfmjg720642cvpxwyqh35kpn";
+
+type Deps = OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>;
+
+fn query_operations(deps: &Deps, msg: QueryMsg) -> Vec<Operation> {
+    from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+#[test]
+fn it_logs_register_wrap_and_redeem() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let height = env.block.height;
+
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(100),
+    )
+    .unwrap();
+    confirm_submessages(&mut deps, &env, &response);
+    let denom = get_denom(&env.contract.address, &Addr::unchecked(CW_20_ADDRESS));
+    let response = handle_redeem_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(SENDER, &[Coin::new(40, denom)]),
+        Some(RECIPIENT.to_string()),
+        None,
+    )
+    .unwrap();
+    confirm_submessages(&mut deps, &env, &response);
+
+    let expected = vec![
+        Operation {
+            seq: 0,
+            height,
+            kind: OperationKind::Register,
+            cw20: Addr::unchecked(CW_20_ADDRESS),
+            amount: Uint128::zero(),
+            from: Addr::unchecked(SENDER),
+            to: None,
+        },
+        Operation {
+            seq: 1,
+            height,
+            kind: OperationKind::Wrap,
+            cw20: Addr::unchecked(CW_20_ADDRESS),
+            amount: Uint128::new(100),
+            from: Addr::unchecked(SENDER),
+            to: Some(Addr::unchecked(SENDER)),
+        },
+        Operation {
+            seq: 2,
+            height,
+            kind: OperationKind::Redeem,
+            cw20: Addr::unchecked(CW_20_ADDRESS),
+            amount: Uint128::new(40),
+            from: Addr::unchecked(SENDER),
+            to: Some(Addr::unchecked(RECIPIENT)),
+        },
+    ];
+    let by_token = query_operations(
+        &deps,
+        QueryMsg::OperationsByToken {
+            addr: CW_20_ADDRESS.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert_eq!(by_token, expected, "incorrect operations of token");
+
+    let by_recipient = query_operations(
+        &deps,
+        QueryMsg::OperationsByAddress {
+            address: RECIPIENT.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert_eq!(by_recipient, vec![expected[2].clone()], "incorrect operations of recipient");
+}
+
+#[test]
+fn it_paginates_operations_by_token_and_address() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let sender = Addr::unchecked(SENDER);
+    for i in 0..6u64 {
+        let cw20 = Addr::unchecked(if i % 2 == 0 { CW_20_ADDRESS } else { OTHER_CW20 });
+        record_operation(
+            &mut deps.storage,
+            &env,
+            OperationKind::Wrap,
+            &cw20,
+            Uint128::new(i as u128),
+            &sender,
+            Some(&sender),
+        )
+        .unwrap();
+    }
+
+    let page = query_operations(
+        &deps,
+        QueryMsg::OperationsByToken {
+            addr: OTHER_CW20.to_string(),
+            start_after: Some(1),
+            limit: Some(1),
+        },
+    );
+    assert_eq!(
+        page.iter().map(|o| o.seq).collect::<Vec<u64>>(),
+        vec![3],
+        "incorrect page of token operations"
+    );
+
+    let page = query_operations(
+        &deps,
+        QueryMsg::OperationsByAddress {
+            address: SENDER.to_string(),
+            start_after: Some(2),
+            limit: Some(2),
+        },
+    );
+    assert_eq!(
+        page.iter().map(|o| o.seq).collect::<Vec<u64>>(),
+        vec![3, 4],
+        "incorrect page of address operations"
+    );
+}
+
+#[test]
+fn it_drops_oldest_operation_once_log_is_full() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let sender = Addr::unchecked(SENDER);
+    let recipient = Addr::unchecked(RECIPIENT);
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+    for _ in 0..=MAX_LOGGED_OPERATIONS {
+        record_operation(
+            &mut deps.storage,
+            &env,
+            OperationKind::Redeem,
+            &cw20,
+            Uint128::one(),
+            &sender,
+            Some(&recipient),
+        )
+        .unwrap();
+    }
+
+    let oldest = query_operations(
+        &deps,
+        QueryMsg::OperationsByToken {
+            addr: CW_20_ADDRESS.to_string(),
+            start_after: None,
+            limit: Some(1),
+        },
+    );
+    assert_eq!(oldest[0].seq, 1, "oldest operation was not dropped");
+    assert!(
+        !ADDRESS_OPERATIONS.has(&deps.storage, (&recipient, 0)),
+        "address index of dropped operation was not removed"
+    );
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary, testing::mock_env, to_binary, Addr, BalanceResponse, Binary, Coin, ContractResult, QuerierResult, SystemResult, Uint128,
+};
+
+use cw20_adapter::{
+    common::get_denom,
+    contract::query,
+    msg::{PortfolioEntry, PortfolioResponse, QueryMsg},
+    state::CW20_CONTRACTS,
+};
+use injective_cosmwasm::{HandlesBankBalanceQuery, HandlesSmartQuery, WasmMockQuerier};
+
+use crate::common::{mock_dependencies, CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER};
+
+const OTHER_CW20: &str = "inj1h0y3hssxf4vsdacfmjg720642cvpxwyqh35kpn";
+const EMPTY_CW20: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+const PAUSED_CW20: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+
+/// CW-20 balance of every holder is 100 for `CW_20_ADDRESS` and zero otherwise. `PAUSED_CW20` fails to answer
+fn create_cw20_balance_query_handler() -> Option<Box<dyn HandlesSmartQuery>> {
+    struct A();
+    impl HandlesSmartQuery for A {
+        fn handle(&self, addr: &str, _: &Binary) -> QuerierResult {
+            if addr == PAUSED_CW20 {
+                return SystemResult::Ok(ContractResult::Err("paused".to_string()));
+            }
+            let balance = if addr == CW_20_ADDRESS { Uint128::new(100) } else { Uint128::zero() };
+            SystemResult::Ok(ContractResult::from(to_binary(&cw20::BalanceResponse { balance })))
+        }
+    }
+    Some(Box::new(A()))
+}
+
+/// Bank balance of every holder is 7 for the TF denom of `OTHER_CW20` and zero otherwise
+fn create_tf_balance_query_handler() -> Option<Box<dyn HandlesBankBalanceQuery>> {
+    struct A();
+    impl HandlesBankBalanceQuery for A {
+        fn handle(&self, _: String, denom: String) -> QuerierResult {
+            let other_denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(OTHER_CW20));
+            let amount = if denom == other_denom { 7 } else { 0 };
+            SystemResult::Ok(ContractResult::from(to_binary(&BalanceResponse {
+                amount: Coin::new(amount, denom),
+            })))
+        }
+    }
+    Some(Box::new(A()))
+}
+
+fn query_portfolio(cw20s: &[&str], hide_zero: Option<bool>, start_after: Option<String>, limit: Option<u32>) -> PortfolioResponse {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_balance_query_handler(),
+        balance_query_handler: create_tf_balance_query_handler(),
+        ..Default::default()
+    };
+    for cw20 in cw20s {
+        CW20_CONTRACTS.insert(&mut deps.storage, cw20).unwrap();
+    }
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    let msg = QueryMsg::Portfolio {
+        address: SENDER.to_string(),
+        start_after,
+        limit,
+        hide_zero,
+    };
+    from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap()
+}
+
+fn entry(cw20: &str, cw20_balance: u128, tf_balance: u128) -> PortfolioEntry {
+    PortfolioEntry {
+        cw20: cw20.to_string(),
+        denom: get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(cw20)),
+        cw20_balance: Some(Uint128::new(cw20_balance)),
+        tf_balance: Uint128::new(tf_balance),
+    }
+}
+
+#[test]
+fn it_returns_balances_of_all_registered_tokens() {
+    let portfolio = query_portfolio(&[CW_20_ADDRESS, OTHER_CW20, EMPTY_CW20], None, None, None);
+    assert_eq!(
+        portfolio.entries,
+        vec![entry(EMPTY_CW20, 0, 0), entry(OTHER_CW20, 0, 7), entry(CW_20_ADDRESS, 100, 0)],
+        "incorrect portfolio"
+    );
+    assert_eq!(portfolio.next_start_after, None, "last page has a cursor");
+}
+
+#[test]
+fn it_hides_tokens_without_balance() {
+    let cw20s = [CW_20_ADDRESS, OTHER_CW20, EMPTY_CW20];
+    let portfolio = query_portfolio(&cw20s, Some(true), None, Some(1));
+    assert_eq!(portfolio.entries, vec![entry(OTHER_CW20, 0, 7)], "zero balance token was not skipped");
+    assert_eq!(portfolio.next_start_after, Some(OTHER_CW20.to_string()), "incorrect cursor");
+
+    let portfolio = query_portfolio(&cw20s, Some(true), portfolio.next_start_after, None);
+    assert_eq!(portfolio.entries, vec![entry(CW_20_ADDRESS, 100, 0)], "incorrect next page");
+}
+
+#[test]
+fn it_marks_tokens_failing_balance_query() {
+    let portfolio = query_portfolio(&[CW_20_ADDRESS, PAUSED_CW20], Some(true), None, None);
+    assert_eq!(
+        portfolio.entries,
+        vec![
+            entry(CW_20_ADDRESS, 100, 0),
+            PortfolioEntry {
+                cw20_balance: None,
+                ..entry(PAUSED_CW20, 0, 0)
+            }
+        ],
+        "incorrect portfolio"
+    );
+}
+
+#[test]
+fn it_bounds_tokens_checked_per_query() {
+    let cw20s = (0..40).map(|i| format!("inj1empty{:02}", i)).collect::<Vec<String>>();
+    let cw20s = cw20s.iter().map(String::as_str).collect::<Vec<&str>>();
+
+    let portfolio = query_portfolio(&cw20s, Some(true), None, None);
+    assert!(portfolio.entries.is_empty(), "zero balance tokens were returned");
+    assert_eq!(portfolio.next_start_after, Some(cw20s[29].to_string()), "scan was not bounded");
+
+    let portfolio = query_portfolio(&cw20s, Some(true), portfolio.next_start_after, None);
+    assert_eq!(portfolio.next_start_after, None, "last page has a cursor");
+}
+
+#[test]
+fn it_returns_at_least_one_token_per_page() {
+    let cw20s = [CW_20_ADDRESS, OTHER_CW20, EMPTY_CW20];
+    let portfolio = query_portfolio(&cw20s, None, Some(EMPTY_CW20.to_string()), Some(0));
+    assert_eq!(portfolio.entries, vec![entry(OTHER_CW20, 0, 7)], "zero limit returned an empty page");
+    assert_eq!(portfolio.next_start_after, Some(OTHER_CW20.to_string()), "cursor did not advance");
+}
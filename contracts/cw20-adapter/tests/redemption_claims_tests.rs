@@ -0,0 +1,308 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info, MockApi, MockStorage},
+    to_binary, Addr, Coin, CosmosMsg, Env, Event, OwnedDeps, Reply, SubMsgResponse, SubMsgResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use cw20_adapter::{
+    common::get_denom,
+    contract::{query, reply},
+    error::ContractError,
+    execute_redeem::handle_redeem_or_claim_msg,
+    execute_redemption_claims::{handle_claim_redemption, handle_process_redemption_claims, record_redemption_claim},
+    msg::{ProcessRedemptionClaimsResponse, QueryMsg, RedemptionClaim, StatsResponse},
+    state::{Config, BLOCKLIST, CONFIG, CW20_CONTRACTS, TF_SUPPLY},
+};
+use injective_cosmwasm::{InjectiveMsgWrapper, InjectiveQueryWrapper};
+
+use crate::common::{mock_dependencies, AdapterMockQuerier, CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER};
+
+const ADMIN: &str = "inj1qg5ega6dykkxc307y25pecuufrjkxkag6xhp6y";
+const RECIPIENT: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+
+type Deps = OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>;
+
+fn setup() -> Deps {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(ADMIN)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    TF_SUPPLY.save(&mut deps.storage, CW_20_ADDRESS, &Uint128::new(1_000), 0).unwrap();
+    deps
+}
+
+fn adapter_env() -> Env {
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    env
+}
+
+fn transfer(recipient: &str, amount: u128) -> CosmosMsg<InjectiveMsgWrapper> {
+    CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: CW_20_ADDRESS.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(amount),
+        })
+        .unwrap(),
+        funds: vec![],
+    })
+}
+
+fn claims(deps: &Deps) -> Vec<RedemptionClaim> {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::RedemptionClaims {
+                addr: CW_20_ADDRESS.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn stats(deps: &Deps) -> StatsResponse {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Stats {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn it_records_claim_and_still_burns_if_transfer_fails() {
+    let mut deps = setup();
+    let denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS));
+    let response = handle_redeem_or_claim_msg(
+        deps.as_mut(),
+        adapter_env(),
+        mock_info(SENDER, &[Coin::new(400, denom.as_str())]),
+        Some(RECIPIENT.to_string()),
+    )
+    .unwrap();
+    assert_eq!(response.messages[0].msg, transfer(RECIPIENT, 400), "incorrect transfer message");
+
+    let failed_transfer = Reply {
+        id: response.messages[0].id,
+        result: SubMsgResult::Err("transfers paused".to_string()),
+    };
+    let reply_response = reply(deps.as_mut(), adapter_env(), failed_transfer).unwrap();
+    assert_eq!(
+        reply_response.events,
+        vec![Event::new("defer_redemption")
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("claimant", RECIPIENT)
+            .add_attribute("amount", "400")
+            .add_attribute("reason", "transfers paused")],
+        "incorrect events emitted"
+    );
+    let burned = Reply {
+        id: response.messages[1].id,
+        result: SubMsgResult::Ok(SubMsgResponse { events: vec![], data: None }),
+    };
+    reply(deps.as_mut(), adapter_env(), burned).unwrap();
+
+    assert_eq!(
+        claims(&deps),
+        vec![RedemptionClaim {
+            claimant: Addr::unchecked(RECIPIENT),
+            amount: Uint128::new(400),
+        }],
+        "claim was not recorded"
+    );
+    let claim: Uint128 = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::RedemptionClaim {
+                addr: CW_20_ADDRESS.to_string(),
+                claimant: RECIPIENT.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(claim, Uint128::new(400), "incorrect claim returned");
+
+    let stats = stats(&deps);
+    assert_eq!(stats.tokens[0].locked, Uint128::new(600), "burned tokens should not be locked");
+    assert_eq!(stats.tokens[0].claimed, Uint128::new(400), "claim was not counted");
+    assert_eq!(stats.total_claimed, Uint128::new(400), "claim was not counted adapter-wide");
+}
+
+#[test]
+fn it_pays_out_claim_to_claimant() {
+    let mut deps = setup();
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+    record_redemption_claim(&mut deps.storage, &cw20, &Addr::unchecked(RECIPIENT), Uint128::new(400)).unwrap();
+
+    let err = handle_claim_redemption(deps.as_mut(), mock_info(SENDER, &[]), cw20.clone()).unwrap_err();
+    assert_eq!(err, ContractError::NoRedemptionClaims, "claim of another address was paid out");
+
+    let response = handle_claim_redemption(deps.as_mut(), mock_info(RECIPIENT, &[]), cw20.clone()).unwrap();
+    assert_eq!(response.messages.len(), 1, "incorrect number of messages returned");
+    assert_eq!(response.messages[0].msg, transfer(RECIPIENT, 400), "incorrect transfer message");
+    assert_eq!(
+        response.events,
+        vec![Event::new("claim_redemption")
+            .add_attribute("sender", RECIPIENT)
+            .add_attribute("cw20", CW_20_ADDRESS)
+            .add_attribute("claimant", RECIPIENT)
+            .add_attribute("amount", "400")],
+        "incorrect events emitted"
+    );
+    assert!(claims(&deps).is_empty(), "claim was not removed");
+    assert_eq!(stats(&deps).total_claimed, Uint128::zero(), "claim is still counted");
+
+    let err = handle_claim_redemption(deps.as_mut(), mock_info(RECIPIENT, &[]), cw20).unwrap_err();
+    assert_eq!(err, ContractError::NoRedemptionClaims, "claim was paid out twice");
+}
+
+#[test]
+fn it_lets_admin_process_claims_page_by_page() {
+    let mut deps = setup();
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+    for (claimant, amount) in [(SENDER, 100), (RECIPIENT, 200)] {
+        record_redemption_claim(&mut deps.storage, &cw20, &Addr::unchecked(claimant), Uint128::new(amount)).unwrap();
+    }
+
+    let err = handle_process_redemption_claims(deps.as_mut(), mock_info(SENDER, &[]), cw20.clone(), None, None).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "incorrect error returned");
+
+    let first = SENDER.min(RECIPIENT);
+    let response = handle_process_redemption_claims(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), None, Some(1)).unwrap();
+    assert_eq!(response.messages.len(), 1, "limit was not applied");
+    assert_eq!(
+        response.messages[0].msg,
+        transfer(first, if first == SENDER { 100 } else { 200 }),
+        "incorrect transfer message"
+    );
+    let processed: ProcessRedemptionClaimsResponse = from_binary(&response.data.unwrap()).unwrap();
+    assert_eq!(
+        processed,
+        ProcessRedemptionClaimsResponse {
+            paid: vec![Addr::unchecked(first)],
+            next_start_after: Some(first.to_string()),
+        },
+        "incorrect response data"
+    );
+    assert_eq!(
+        response.events.last(),
+        Some(
+            &Event::new("process_redemption_claims")
+                .add_attribute("sender", ADMIN)
+                .add_attribute("cw20", CW_20_ADDRESS)
+                .add_attribute("checked", "1")
+                .add_attribute("paid", "1")
+                .add_attribute("next_start_after", first)
+        ),
+        "incorrect summary event"
+    );
+
+    let response = handle_process_redemption_claims(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), processed.next_start_after, None).unwrap();
+    let processed: ProcessRedemptionClaimsResponse = from_binary(&response.data.unwrap()).unwrap();
+    assert_eq!(processed.next_start_after, None, "last page has a cursor");
+    assert!(claims(&deps).is_empty(), "claims were not removed");
+    let err = handle_process_redemption_claims(deps.as_mut(), mock_info(ADMIN, &[]), cw20, None, None).unwrap_err();
+    assert_eq!(err, ContractError::NoRedemptionClaims, "incorrect error returned");
+}
+
+#[test]
+fn it_lets_admin_step_over_failing_claims() {
+    let mut deps = setup();
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+    for (claimant, amount) in [(SENDER, 100), (RECIPIENT, 200)] {
+        record_redemption_claim(&mut deps.storage, &cw20, &Addr::unchecked(claimant), Uint128::new(amount)).unwrap();
+    }
+    let (first, second) = (SENDER.min(RECIPIENT), SENDER.max(RECIPIENT));
+
+    // state changes of a failed message are reverted by the chain, so the failing claimant is only named in the error
+    let response = handle_process_redemption_claims(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), None, None).unwrap();
+    let failed_transfer = Reply {
+        id: response.messages[0].id,
+        result: SubMsgResult::Err("transfers paused".to_string()),
+    };
+    let err = reply(deps.as_mut(), adapter_env(), failed_transfer).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ReleaseFailed {
+            cw20: CW_20_ADDRESS.to_string(),
+            recipient: first.to_string(),
+            reason: "transfers paused".to_string(),
+        },
+        "failing claimant was not reported"
+    );
+    for (claimant, amount) in [(SENDER, 100), (RECIPIENT, 200)] {
+        record_redemption_claim(&mut deps.storage, &cw20, &Addr::unchecked(claimant), Uint128::new(amount)).unwrap();
+    }
+
+    let response = handle_process_redemption_claims(deps.as_mut(), mock_info(ADMIN, &[]), cw20.clone(), Some(first.to_string()), None).unwrap();
+    assert_eq!(
+        response.messages.iter().map(|m| m.msg.clone()).collect::<Vec<_>>(),
+        vec![transfer(second, if second == SENDER { 100 } else { 200 })],
+        "claims after start_after were not paid out"
+    );
+    assert_eq!(
+        claims(&deps).into_iter().map(|c| c.claimant).collect::<Vec<_>>(),
+        vec![Addr::unchecked(first)],
+        "claim before start_after was not kept"
+    );
+
+    let err = handle_process_redemption_claims(deps.as_mut(), mock_info(ADMIN, &[]), cw20, Some(second.to_string()), None).unwrap_err();
+    assert_eq!(err, ContractError::NoRedemptionClaims, "incorrect error returned");
+}
+
+#[test]
+fn it_keeps_claims_of_blocked_claimants() {
+    let mut deps = setup();
+    let cw20 = Addr::unchecked(CW_20_ADDRESS);
+    for (claimant, amount) in [(SENDER, 100), (RECIPIENT, 200)] {
+        record_redemption_claim(&mut deps.storage, &cw20, &Addr::unchecked(claimant), Uint128::new(amount)).unwrap();
+    }
+    BLOCKLIST.insert(&mut deps.storage, &Addr::unchecked(RECIPIENT)).unwrap();
+
+    let err = handle_claim_redemption(deps.as_mut(), mock_info(RECIPIENT, &[]), cw20.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::AddressBlocked {
+            address: RECIPIENT.to_string()
+        },
+        "blocked claimant was paid out"
+    );
+
+    let response = handle_process_redemption_claims(deps.as_mut(), mock_info(ADMIN, &[]), cw20, None, None).unwrap();
+    assert_eq!(
+        response.messages.iter().map(|m| m.msg.clone()).collect::<Vec<_>>(),
+        vec![transfer(SENDER, 100)],
+        "blocked claimant was paid out by admin"
+    );
+    assert_eq!(
+        claims(&deps),
+        vec![RedemptionClaim {
+            claimant: Addr::unchecked(RECIPIENT),
+            amount: Uint128::new(200),
+        }],
+        "claim of blocked claimant was not kept"
+    );
+}
@@ -0,0 +1,240 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info, MockApi, MockStorage},
+    Addr, Coin, OwnedDeps, Uint128,
+};
+
+use cw20_adapter::{
+    contract::query,
+    error::ContractError,
+    execute_metadata::refresh_stale_metadata,
+    execute_receive::handle_on_received_cw20_funds_msg,
+    execute_token_restrictions::handle_set_token_verified,
+    msg::{QueryMsg, RegisteredToken},
+    state::{registrations, Config, CONFIG},
+};
+use injective_cosmwasm::{InjectiveQueryWrapper, WasmMockQuerier};
+
+use crate::common::{
+    confirm_submessages, create_custom_bank_balance_query_handler, create_cw20_info_query_handler, create_renamed_cw20_info_query_handler,
+    mock_dependencies, AdapterMockQuerier, CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER,
+};
+
+const OTHER_SENDER: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+const OTHER_CW20: &str = "inj1h0y3hssxf4vsdacfmjg720642cvpxwyqh35kpn";
+
+type Deps = OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>;
+
+/// Both tokens are reported by the mock as "Solana" (SOL)
+fn deps_with_two_registrations() -> Deps {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    for (cw20, depositor) in [(CW_20_ADDRESS, SENDER), (OTHER_CW20, OTHER_SENDER)] {
+        let response =
+            handle_on_received_cw20_funds_msg(deps.as_mut(), env.clone(), mock_info(cw20, &[]), depositor.to_string(), Uint128::new(1)).unwrap();
+        confirm_submessages(&mut deps, &env, &response);
+    }
+    deps
+}
+
+fn query_registered_tokens(deps: &Deps, msg: QueryMsg) -> Vec<RegisteredToken> {
+    from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+fn query_cw20s(deps: &Deps, msg: QueryMsg) -> Vec<String> {
+    query_registered_tokens(deps, msg).into_iter().map(|t| t.cw20).collect()
+}
+
+#[test]
+fn it_finds_registrations_by_registrant() {
+    let deps = deps_with_two_registrations();
+    let tokens = query_cw20s(
+        &deps,
+        QueryMsg::RegistrationsByRegistrant {
+            registrant: OTHER_SENDER.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert_eq!(tokens, vec![OTHER_CW20.to_string()], "incorrect tokens of registrant");
+}
+
+#[test]
+fn it_finds_registrations_by_normalised_symbol_and_name() {
+    let deps = deps_with_two_registrations();
+    let tokens = query_cw20s(
+        &deps,
+        QueryMsg::RegistrationsBySymbol {
+            symbol: " sol ".to_string(),
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert_eq!(
+        tokens,
+        vec![OTHER_CW20.to_string(), CW_20_ADDRESS.to_string()],
+        "incorrect tokens with symbol"
+    );
+
+    let tokens = query_cw20s(
+        &deps,
+        QueryMsg::RegistrationsByName {
+            name: "SOLANA".to_string(),
+            start_after: Some(OTHER_CW20.to_string()),
+            limit: Some(1),
+        },
+    );
+    assert_eq!(tokens, vec![CW_20_ADDRESS.to_string()], "incorrect page of tokens with name");
+
+    let tokens = query_cw20s(
+        &deps,
+        QueryMsg::RegistrationsBySymbol {
+            symbol: "ETH".to_string(),
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert!(tokens.is_empty(), "unexpected tokens with unknown symbol");
+}
+
+#[test]
+fn it_flags_symbol_and_name_collisions() {
+    let deps = deps_with_two_registrations();
+    let first = registrations().load(&deps.storage, CW_20_ADDRESS).unwrap();
+    assert!(
+        !first.symbol_collision && !first.name_collision,
+        "first registration should not be flagged"
+    );
+    let second = registrations().load(&deps.storage, OTHER_CW20).unwrap();
+    assert!(second.symbol_collision, "symbol collision was not flagged");
+    assert!(second.name_collision, "name collision was not flagged");
+}
+
+#[test]
+fn it_follows_renamed_cw20_on_metadata_refresh() {
+    let mut deps = deps_with_two_registrations();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                metadata_refresh_blocks: 1,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    env.block.height += 1;
+
+    deps.querier.base.smart_query_handler = create_renamed_cw20_info_query_handler(vec![OTHER_CW20]);
+    refresh_stale_metadata(deps.as_mut(), &env, &Addr::unchecked(OTHER_CW20)).unwrap();
+    let renamed = registrations().load(&deps.storage, OTHER_CW20).unwrap();
+    assert_eq!(
+        (renamed.name.as_str(), renamed.symbol.as_str()),
+        ("Wrapped Ether", "WETH"),
+        "registration was not renamed"
+    );
+    assert!(
+        !renamed.symbol_collision && !renamed.name_collision,
+        "collisions of the old labels were kept"
+    );
+    let symbol_query = |symbol: &str| QueryMsg::RegistrationsBySymbol {
+        symbol: symbol.to_string(),
+        start_after: None,
+        limit: None,
+    };
+    assert_eq!(
+        query_cw20s(&deps, symbol_query("SOL")),
+        vec![CW_20_ADDRESS.to_string()],
+        "old symbol was not unindexed"
+    );
+    assert_eq!(
+        query_cw20s(&deps, symbol_query("WETH")),
+        vec![OTHER_CW20.to_string()],
+        "new symbol was not indexed"
+    );
+
+    deps.querier.base.smart_query_handler = create_renamed_cw20_info_query_handler(vec![CW_20_ADDRESS, OTHER_CW20]);
+    refresh_stale_metadata(deps.as_mut(), &env, &Addr::unchecked(CW_20_ADDRESS)).unwrap();
+    let spoof = registrations().load(&deps.storage, CW_20_ADDRESS).unwrap();
+    assert!(spoof.symbol_collision, "symbol collision of new label was not flagged");
+    assert!(spoof.name_collision, "name collision of new label was not flagged");
+
+    refresh_stale_metadata(deps.as_mut(), &env, &Addr::unchecked(OTHER_CW20)).unwrap();
+    let unchanged = registrations().load(&deps.storage, OTHER_CW20).unwrap();
+    assert!(!unchanged.symbol_collision, "registration collided with itself");
+}
+
+#[test]
+fn it_sets_verified_badge() {
+    let mut deps = deps_with_two_registrations();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                admin: Some(Addr::unchecked(SENDER)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+    let err = handle_set_token_verified(deps.as_mut(), mock_info(OTHER_SENDER, &[]), Addr::unchecked(CW_20_ADDRESS), true).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized, "non-admin verified a token");
+
+    handle_set_token_verified(deps.as_mut(), mock_info(SENDER, &[]), Addr::unchecked(CW_20_ADDRESS), true).unwrap();
+    let tokens = query_registered_tokens(
+        &deps,
+        QueryMsg::RegistrationsBySymbol {
+            symbol: "SOL".to_string(),
+            start_after: None,
+            limit: None,
+        },
+    );
+    let verified: Vec<(&str, bool)> = tokens.iter().map(|t| (t.cw20.as_str(), t.verified)).collect();
+    assert_eq!(verified, vec![(OTHER_CW20, false), (CW_20_ADDRESS, true)], "incorrect verified badges");
+
+    let verified: Vec<String> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VerifiedTokens {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(verified, vec![CW_20_ADDRESS.to_string()], "incorrect verified tokens");
+    let token: RegisteredToken = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Registration {
+                addr: CW_20_ADDRESS.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(token.verified, "registration query has no verified badge");
+
+    handle_set_token_verified(deps.as_mut(), mock_info(SENDER, &[]), Addr::unchecked(CW_20_ADDRESS), false).unwrap();
+    let tokens = query_registered_tokens(
+        &deps,
+        QueryMsg::RegistrationsByRegistrant {
+            registrant: SENDER.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    );
+    assert!(!tokens[0].verified, "verified badge was not cleared");
+}
@@ -0,0 +1,249 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info, MockApi, MockStorage},
+    Addr, Coin, Env, OwnedDeps, Reply, SubMsgResponse, SubMsgResult, Uint128,
+};
+
+use cw20_adapter::{
+    common::get_denom,
+    contract::{query, reply},
+    error::ContractError,
+    execute_receive::handle_on_received_cw20_funds_msg,
+    execute_redeem::handle_redeem_msg,
+    execute_register::handle_register_msg,
+    msg::{FeeBalance, QueryMsg},
+    state::{Config, FeeSettings, CONFIG, CW20_CONTRACTS, PENDING_REPLIES, TF_SUPPLY},
+};
+use injective_cosmwasm::{InjectiveQueryWrapper, WasmMockQuerier};
+
+use crate::common::{
+    create_custom_bank_balance_query_handler, create_cw20_info_query_handler, mock_dependencies, AdapterMockQuerier, CONTRACT_ADDRESS, CW_20_ADDRESS,
+    SENDER,
+};
+
+const TREASURY: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+
+type Deps = OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>;
+
+fn adapter_env() -> Env {
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    env
+}
+
+fn succeeded(id: u64) -> Reply {
+    Reply {
+        id,
+        result: SubMsgResult::Ok(SubMsgResponse { events: vec![], data: None }),
+    }
+}
+
+fn failed(id: u64, reason: &str) -> Reply {
+    Reply {
+        id,
+        result: SubMsgResult::Err(reason.to_string()),
+    }
+}
+
+fn deps_with_registered_cw20() -> Deps {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                treasury: Some(Addr::unchecked(TREASURY)),
+                default_fees: FeeSettings {
+                    wrap_fee_bps: 0,
+                    unwrap_fee_bps: 100,
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    TF_SUPPLY.save(&mut deps.storage, CW_20_ADDRESS, &Uint128::new(1_000), 0).unwrap();
+    deps
+}
+
+fn fee_balances(deps: &Deps) -> Vec<FeeBalance> {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FeeBalances {
+                beneficiary: TREASURY.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn it_keeps_registration_pending_if_denom_creation_fails() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let response = handle_register_msg(
+        deps.as_mut(),
+        adapter_env(),
+        mock_info(SENDER, &[Coin::new(10, "inj")]),
+        Addr::unchecked(CW_20_ADDRESS),
+        None,
+    )
+    .unwrap();
+
+    let create_denom_id = response.messages[0].id;
+    let err = reply(deps.as_mut(), adapter_env(), failed(create_denom_id, "denom exists")).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DenomCreationFailed {
+            cw20: CW_20_ADDRESS.to_string(),
+            reason: "denom exists".to_string(),
+        },
+        "incorrect error returned"
+    );
+    assert!(!CW20_CONTRACTS.contains(&deps.storage, CW_20_ADDRESS), "registration was activated");
+}
+
+#[test]
+fn it_refuses_mint_confirmed_before_denom_creation() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        adapter_env(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(100),
+    )
+    .unwrap();
+    assert_eq!(response.messages.len(), 3, "incorrect number of messages returned");
+
+    let mint_id = response.messages[2].id;
+    let err = reply(deps.as_mut(), adapter_env(), succeeded(mint_id)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::DenomNotCreated {
+            cw20: CW_20_ADDRESS.to_string()
+        },
+        "incorrect error returned"
+    );
+}
+
+#[test]
+fn it_reports_failed_mint() {
+    let mut deps = deps_with_registered_cw20();
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        adapter_env(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(100),
+    )
+    .unwrap();
+
+    let err = reply(deps.as_mut(), adapter_env(), failed(response.messages[0].id, "not enough gas")).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MintFailed {
+            cw20: CW_20_ADDRESS.to_string(),
+            reason: "not enough gas".to_string(),
+        },
+        "incorrect error returned"
+    );
+}
+
+#[test]
+fn it_commits_redemption_accounting_once_burn_succeeds() {
+    let mut deps = deps_with_registered_cw20();
+    let denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS));
+    let response = handle_redeem_msg(
+        deps.as_mut(),
+        adapter_env(),
+        mock_info(SENDER, &[Coin::new(500, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap();
+    let (release_id, burn_id) = (response.messages[0].id, response.messages[1].id);
+
+    reply(deps.as_mut(), adapter_env(), succeeded(release_id)).unwrap();
+    assert_eq!(
+        TF_SUPPLY.load(&deps.storage, CW_20_ADDRESS).unwrap(),
+        Uint128::new(1_000),
+        "supply was decreased before the burn was confirmed"
+    );
+    assert!(fee_balances(&deps).is_empty(), "fees were credited before the burn was confirmed");
+
+    reply(deps.as_mut(), adapter_env(), succeeded(burn_id)).unwrap();
+    assert_eq!(
+        TF_SUPPLY.load(&deps.storage, CW_20_ADDRESS).unwrap(),
+        Uint128::new(500),
+        "incorrect supply"
+    );
+    assert_eq!(
+        fee_balances(&deps),
+        vec![FeeBalance {
+            asset: CW_20_ADDRESS.to_string(),
+            amount: Uint128::new(5)
+        }],
+        "incorrect fees credited"
+    );
+    assert!(!PENDING_REPLIES.has(&deps.storage, burn_id), "pending reply was not removed");
+}
+
+#[test]
+fn it_reports_failed_release_and_burn() {
+    let mut deps = deps_with_registered_cw20();
+    let denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS));
+    let response = handle_redeem_msg(
+        deps.as_mut(),
+        adapter_env(),
+        mock_info(SENDER, &[Coin::new(500, denom.as_str())]),
+        None,
+        None,
+    )
+    .unwrap();
+
+    let err = reply(deps.as_mut(), adapter_env(), failed(response.messages[0].id, "transfers paused")).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ReleaseFailed {
+            cw20: CW_20_ADDRESS.to_string(),
+            recipient: SENDER.to_string(),
+            reason: "transfers paused".to_string(),
+        },
+        "incorrect error returned"
+    );
+
+    let err = reply(deps.as_mut(), adapter_env(), failed(response.messages[1].id, "insufficient funds")).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::BurnFailed {
+            cw20: CW_20_ADDRESS.to_string(),
+            reason: "insufficient funds".to_string(),
+        },
+        "incorrect error returned"
+    );
+    assert_eq!(
+        TF_SUPPLY.load(&deps.storage, CW_20_ADDRESS).unwrap(),
+        Uint128::new(1_000),
+        "supply was decreased by failed redemption"
+    );
+}
+
+#[test]
+fn it_returns_error_on_unexpected_reply() {
+    let mut deps = mock_dependencies();
+    let err = reply(deps.as_mut(), adapter_env(), succeeded(42)).unwrap_err();
+    assert_eq!(err, ContractError::UnexpectedReply { id: 42 }, "incorrect error returned");
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info, MockApi, MockStorage},
+    Addr, Coin, Env, OwnedDeps, Uint128,
+};
+
+use cw20_adapter::{
+    common::get_denom,
+    contract::query,
+    execute_receive::handle_on_received_cw20_funds_msg,
+    execute_redeem::handle_redeem_msg,
+    msg::{QueryMsg, RegisteredContractsAtHeightResponse},
+    state::REGISTRATION_STATUS,
+};
+use injective_cosmwasm::{InjectiveQueryWrapper, WasmMockQuerier};
+
+use crate::common::{
+    confirm_submessages, create_custom_bank_balance_query_handler, create_cw20_info_query_handler, mock_dependencies, AdapterMockQuerier,
+    CONTRACT_ADDRESS, CW_20_ADDRESS, SENDER,
+};
+
+fn env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    env
+}
+
+fn registered_page_at(
+    deps: &OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>,
+    height: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> RegisteredContractsAtHeightResponse {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::RegisteredContractsAtHeight { height, start_after, limit },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn registered_at(deps: &OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>, height: u64) -> Vec<String> {
+    registered_page_at(deps, height, None, None).contracts
+}
+
+fn supply_at(deps: &OwnedDeps<MockStorage, MockApi, AdapterMockQuerier, InjectiveQueryWrapper>, height: u64) -> Uint128 {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SupplyAtHeight {
+                addr: CW_20_ADDRESS.to_string(),
+                height,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn it_answers_registry_and_supply_at_past_heights() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let denom = get_denom(&Addr::unchecked(CONTRACT_ADDRESS), &Addr::unchecked(CW_20_ADDRESS));
+
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env_at(100),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(100),
+    )
+    .unwrap();
+    confirm_submessages(&mut deps, &env_at(100), &response);
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env_at(100),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(20),
+    )
+    .unwrap();
+    confirm_submessages(&mut deps, &env_at(100), &response);
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env_at(105),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(50),
+    )
+    .unwrap();
+    confirm_submessages(&mut deps, &env_at(105), &response);
+    let response = handle_redeem_msg(deps.as_mut(), env_at(110), mock_info(SENDER, &[Coin::new(30, denom)]), None, None).unwrap();
+    confirm_submessages(&mut deps, &env_at(110), &response);
+
+    assert!(
+        registered_at(&deps, 100).is_empty(),
+        "contract should not be registered at the start of registration block"
+    );
+    assert_eq!(
+        registered_at(&deps, 101),
+        vec![CW_20_ADDRESS.to_string()],
+        "contract should be registered after registration block"
+    );
+
+    assert_eq!(supply_at(&deps, 100), Uint128::zero(), "incorrect supply before first wrap");
+    assert_eq!(supply_at(&deps, 101), Uint128::new(120), "incorrect supply after first block");
+    assert_eq!(
+        supply_at(&deps, 105),
+        Uint128::new(120),
+        "incorrect supply at the start of second wrap block"
+    );
+    assert_eq!(supply_at(&deps, 110), Uint128::new(170), "incorrect supply before redeem");
+    assert_eq!(supply_at(&deps, 111), Uint128::new(140), "incorrect current supply");
+}
+
+#[test]
+fn it_paginates_registry_at_past_height() {
+    let mut deps = mock_dependencies();
+    for (i, height) in [(1, 100), (2, 100), (3, 120), (4, 100)] {
+        REGISTRATION_STATUS
+            .save(&mut deps.storage, &format!("inj1{:0>38}", i), &true, height)
+            .unwrap();
+    }
+
+    let page = registered_page_at(&deps, 110, None, Some(3));
+    assert_eq!(
+        page,
+        RegisteredContractsAtHeightResponse {
+            contracts: vec![format!("inj1{:0>38}", 1), format!("inj1{:0>38}", 2)],
+            next_start_after: Some(format!("inj1{:0>38}", 3)),
+        },
+        "incorrect first page"
+    );
+    let page = registered_page_at(&deps, 110, page.next_start_after, Some(3));
+    assert_eq!(
+        page,
+        RegisteredContractsAtHeightResponse {
+            contracts: vec![format!("inj1{:0>38}", 4)],
+            next_start_after: None,
+        },
+        "incorrect last page"
+    );
+    assert_eq!(
+        registered_page_at(&deps, 110, None, Some(0)).contracts,
+        vec![format!("inj1{:0>38}", 1)],
+        "zero limit should return a single contract"
+    );
+}
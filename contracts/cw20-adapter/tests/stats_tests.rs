@@ -0,0 +1,182 @@
+mod common;
+
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info},
+    Addr, Coin, Uint128,
+};
+
+use cw20_adapter::{
+    common::get_denom,
+    contract::query,
+    execute_receive::handle_on_received_cw20_funds_msg,
+    execute_redeem::handle_redeem_msg,
+    msg::{QueryMsg, StatsResponse},
+    state::{Config, FeeSettings, TokenStats, CONFIG, CW20_CONTRACTS},
+};
+use injective_cosmwasm::WasmMockQuerier;
+
+use crate::common::{
+    confirm_submessages, create_custom_bank_balance_query_handler, create_cw20_info_query_handler, mock_dependencies, CONTRACT_ADDRESS,
+    CW_20_ADDRESS, SENDER,
+};
+
+const OTHER_SENDER: &str = "inj1gfawuv6fslzjlfa4v7exv27mk6rpfeyv823eu2";
+const OTHER_CW20: &str = "inj1h0y3hssxf4vsdacfmjg720642cvpxwyqh35kpn";
+
+#[test]
+fn it_aggregates_stats_per_token_and_adapter_wide() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+
+    for (cw20, depositor, amount) in [(CW_20_ADDRESS, SENDER, 100), (CW_20_ADDRESS, SENDER, 50), (OTHER_CW20, OTHER_SENDER, 30)] {
+        let response = handle_on_received_cw20_funds_msg(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(cw20, &[]),
+            depositor.to_string(),
+            Uint128::new(amount),
+        )
+        .unwrap();
+        confirm_submessages(&mut deps, &env, &response);
+    }
+    let denom = get_denom(&env.contract.address, &Addr::unchecked(CW_20_ADDRESS));
+    let response = handle_redeem_msg(deps.as_mut(), env.clone(), mock_info(SENDER, &[Coin::new(40, denom)]), None, None).unwrap();
+    confirm_submessages(&mut deps, &env, &response);
+
+    let stats: StatsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Stats {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        stats.total,
+        TokenStats {
+            wrapped_volume: Uint128::new(180),
+            unwrapped_volume: Uint128::new(40),
+            wrap_count: 3,
+            redeem_count: 1,
+            unique_depositors: 2,
+            ..Default::default()
+        },
+        "incorrect adapter-wide stats"
+    );
+    assert_eq!(stats.total_locked, Uint128::new(140), "incorrect total locked amount");
+
+    let token = stats.tokens.iter().find(|t| t.cw20 == CW_20_ADDRESS).unwrap();
+    assert_eq!(
+        token.stats,
+        TokenStats {
+            wrapped_volume: Uint128::new(150),
+            unwrapped_volume: Uint128::new(40),
+            wrap_count: 2,
+            redeem_count: 1,
+            unique_depositors: 1,
+            ..Default::default()
+        },
+        "incorrect token stats"
+    );
+    assert_eq!(token.locked, Uint128::new(110), "incorrect locked amount of token");
+}
+
+#[test]
+fn it_paginates_token_stats() {
+    let mut deps = mock_dependencies();
+    deps.querier.base = WasmMockQuerier {
+        balance_query_handler: create_custom_bank_balance_query_handler(Coin::new(10, "inj")),
+        smart_query_handler: create_cw20_info_query_handler(),
+        ..Default::default()
+    };
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+    for cw20 in [CW_20_ADDRESS, OTHER_CW20] {
+        let response =
+            handle_on_received_cw20_funds_msg(deps.as_mut(), env.clone(), mock_info(cw20, &[]), SENDER.to_string(), Uint128::new(10)).unwrap();
+        confirm_submessages(&mut deps, &env, &response);
+    }
+
+    let first = CW_20_ADDRESS.min(OTHER_CW20);
+    let stats: StatsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Stats {
+                start_after: Some(first.to_string()),
+                limit: Some(5),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        stats.tokens.iter().map(|t| t.cw20.as_str()).collect::<Vec<&str>>(),
+        vec![CW_20_ADDRESS.max(OTHER_CW20)],
+        "incorrect page of token stats"
+    );
+    assert_eq!(stats.total.wrap_count, 2, "total should not depend on the page");
+}
+
+#[test]
+fn it_counts_collected_fees() {
+    let mut deps = mock_dependencies();
+    CONFIG
+        .save(
+            &mut deps.storage,
+            &Config {
+                treasury: Some(Addr::unchecked(OTHER_SENDER)),
+                default_fees: FeeSettings {
+                    wrap_fee_bps: 100,
+                    unwrap_fee_bps: 200,
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    CW20_CONTRACTS.insert(&mut deps.storage, CW_20_ADDRESS).unwrap();
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked(CONTRACT_ADDRESS);
+
+    let response = handle_on_received_cw20_funds_msg(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(CW_20_ADDRESS, &[]),
+        SENDER.to_string(),
+        Uint128::new(1_000),
+    )
+    .unwrap();
+    confirm_submessages(&mut deps, &env, &response);
+    let denom = get_denom(&env.contract.address, &Addr::unchecked(CW_20_ADDRESS));
+    let response = handle_redeem_msg(deps.as_mut(), env.clone(), mock_info(SENDER, &[Coin::new(500, denom)]), None, None).unwrap();
+    confirm_submessages(&mut deps, &env, &response);
+
+    let stats: StatsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Stats {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(stats.tokens[0].stats.wrap_fees, Uint128::new(10), "incorrect wrap fees of token");
+    assert_eq!(stats.tokens[0].stats.unwrap_fees, Uint128::new(10), "incorrect unwrap fees of token");
+    assert_eq!(stats.total.wrap_fees, Uint128::new(10), "incorrect adapter-wide wrap fees");
+    assert_eq!(stats.total.unwrap_fees, Uint128::new(10), "incorrect adapter-wide unwrap fees");
+}